@@ -1,6 +1,11 @@
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use argos_core::commands::compare::gate::Threshold;
+use argos_core::commands::compare::matching::MatchBy;
+use argos_core::commands::leak::MemoryMetric;
+use argos_core::commands::top::{ProcessFilter, TopSort};
+use argos_core::process::model::DeltaStatus;
+use clap::{Args, Parser, Subcommand};
 #[derive(Parser)]
 #[command(name = "argos")]
 #[command(version = "0.1.0")]
@@ -30,9 +35,17 @@ pub enum Commands {
     
     /// Realiza un muestreo durante varios segundos
     Sample {
-        /// ID del proceso (PID)
-        #[arg(short, long)]
-        pid: u32,
+        /// ID del proceso (PID); admite varios
+        #[arg(short, long, num_args = 1.., required_unless_present = "name")]
+        pid: Vec<u32>,
+
+        /// Nombre de proceso (admite comodines `*`); admite varios
+        #[arg(short, long, num_args = 1..)]
+        name: Vec<String>,
+
+        /// Suma a cada proceso todos sus descendientes en una sola serie
+        #[arg(long)]
+        include_children: bool,
 
         /// Número de iteraciones
         #[arg(long, default_value_t = 10)]
@@ -49,6 +62,10 @@ pub enum Commands {
         /// Archivo de salida (opcional)
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Guarda las muestras en la base de datos (una sesión por proceso)
+        #[arg(long)]
+        save: bool,
     },
     
     /// Muestra historial de procesos
@@ -102,9 +119,21 @@ pub enum Commands {
 
     /// Muestra información en tiempo real de un proceso
     Live {
-        /// ID del proceso (PID)
-        #[arg(short, long)]
-        pid: u32,
+        /// ID del proceso (PID); admite varios
+        #[arg(short, long, num_args = 1.., required_unless_present = "name")]
+        pid: Vec<u32>,
+
+        /// Nombre de proceso (admite comodines `*`); admite varios
+        #[arg(short, long, num_args = 1..)]
+        name: Vec<String>,
+
+        /// Sigue también a los hijos que vayan naciendo de los procesos seguidos
+        #[arg(long)]
+        follow_children: bool,
+
+        /// Intervalo entre iteraciones (milisegundos)
+        #[arg(short = 'i', long, default_value_t = 1000)]
+        interval_ms: u64,
 
         /// Archivo de salida (opcional)
         #[arg(short, long)]
@@ -116,6 +145,88 @@ pub enum Commands {
         
     },
 
+    /// Procesos que más consumen, repetido cada intervalo (como `top`)
+    Top {
+        /// Número de procesos a mostrar
+        #[arg(short, long, default_value_t = 15)]
+        top: usize,
+
+        /// Ordenar por (cpu, memory, vmem, read, write, pid, name, user)
+        #[arg(long, default_value = "cpu")]
+        sort_by: TopSort,
+
+        /// Filtro, p. ej. "cpu > 5", "mem >= 100", "user = root" o
+        /// "name != kworker*". Repetible; deben cumplirse todos
+        #[arg(short = 'w', long = "where", value_name = "FILTRO")]
+        filter: Vec<ProcessFilter>,
+
+        /// Número de iteraciones (por defecto hasta interrumpir con Ctrl+C)
+        #[arg(short = 'n', long)]
+        iterations: Option<usize>,
+
+        /// Intervalo entre iteraciones (milisegundos)
+        #[arg(short = 'i', long, default_value_t = 1000)]
+        interval_ms: u64,
+
+        /// Modo batch (como `top -b`): añade cada iteración debajo en vez de redibujar
+        #[arg(short, long)]
+        batch: bool,
+
+        /// Formato de salida (text, json, csv)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
+    /// Procesos zombie agrupados por padre y huérfanos adoptados por init o un subreaper
+    Zombies {
+        /// Formato de salida (text, json, csv)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Archivo de salida (opcional)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Detecta fugas de memoria: muestrea un proceso y ajusta la tendencia
+    Leakcheck {
+        /// ID del proceso (PID)
+        #[arg(short, long)]
+        pid: u32,
+
+        /// Duración del muestreo (p. ej. 90s, 10m, 2h)
+        #[arg(short, long, default_value = "10m")]
+        duration: String,
+
+        /// Tramo inicial que no entra en el ajuste (por defecto, el 10 % de la duración)
+        #[arg(short, long)]
+        warmup: Option<String>,
+
+        /// Intervalo entre muestras (milisegundos)
+        #[arg(short = 'i', long, default_value_t = 1000)]
+        interval_ms: u64,
+
+        /// Memoria a seguir (rss, pss)
+        #[arg(short, long, default_value = "rss")]
+        metric: MemoryMetric,
+
+        /// Límite para proyectar cuándo se alcanza (p. ej. 512M, 2G; por defecto, la memoria total)
+        #[arg(short, long)]
+        limit: Option<String>,
+
+        /// Falla también con "posible fuga" (por defecto, solo con fuga)
+        #[arg(long)]
+        strict: bool,
+
+        /// Formato de salida (text, json, csv)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Archivo de salida (opcional)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
     /// Comparacion entre dos procesos
     Compare {
         /// IDs de los procesos (PIDs)
@@ -126,6 +237,15 @@ pub enum Commands {
         #[arg(long, num_args = 1.., value_delimiter= ' ', conflicts_with("pids"))]
         files: Option<Vec<PathBuf>>,
 
+        /// Comparación estadística A/B de dos sesiones de muestreo (ID de sesión
+        /// en la base de datos o archivo exportado con `sample`)
+        #[arg(long, num_args = 2, value_names = ["A", "B"], conflicts_with_all = ["pids", "files", "fail_if"])]
+        sessions: Option<Vec<String>>,
+
+        /// Nivel de confianza de la comparación A/B
+        #[arg(long, default_value = "0.95", requires = "sessions")]
+        confidence: f64,
+
         /// Formato de salida (text, json, csv)
         #[arg(short, long, default_value = "text")]
         format: String,
@@ -136,30 +256,111 @@ pub enum Commands {
 
         /// Intervalo en milisegundos
         #[arg(short, long, default_value = "300")]
-        interval: u64
+        interval: u64,
+
+        /// Cómo emparejar procesos entre muestras (pid, name, cmd, exe). Con name, cmd
+        /// o exe se suman los procesos que comparten clave y se muestran las instancias
+        #[arg(long, default_value = "pid")]
+        match_by: MatchBy,
+
+        /// Muestra solo los procesos en estos estados (added, removed, changed, unchanged)
+        #[arg(long, value_delimiter = ',', value_name = "ESTADOS")]
+        only: Vec<DeltaStatus>,
+
+        /// Variación de CPU (puntos) por debajo de la cual un proceso no cambió
+        #[arg(long, default_value = "1.0")]
+        cpu_tolerance: f64,
+
+        /// Variación de memoria (MB) por debajo de la cual un proceso no cambió
+        #[arg(long, default_value = "1.0")]
+        mem_tolerance: f64,
+
+        /// Falla (código de salida 2) si algún proceso cumple el umbral, p. ej.
+        /// "mem_delta_pct > 10" o "cpu_after > 50". Repetible
+        #[arg(long = "fail-if", value_name = "UMBRAL")]
+        fail_if: Vec<Threshold>,
+
+        /// Escribe el resultado de los umbrales como JUnit XML
+        #[arg(long, value_name = "ARCHIVO", requires = "fail_if")]
+        junit: Option<PathBuf>,
     },
 
-    /// Genera logs y reportes de auditoría
-    Watchdog {
-        /// ID del proceso a monitorear (PID)
+    /// Guarda un snapshot de todos los procesos con los datos del equipo
+    Snapshot {
+        /// Archivo de salida
         #[arg(short, long)]
-        pid: u32,
+        output: PathBuf,
 
-        /// Umbral de CPU para activar la alerta
+        /// Comprime con gzip (automático si el archivo termina en .gz)
         #[arg(long)]
-        cpu_over: Option<f32>,
+        gzip: bool,
+    },
+
+    /// Ejecuta un comando y lo perfila hasta que termina (como `time -v`)
+    Run {
+        /// Intervalo entre muestras (milisegundos)
+        #[arg(short = 'i', long, default_value_t = 100)]
+        interval_ms: u64,
+
+        /// Formato del resumen y del archivo de salida (text, json, csv)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Guarda el perfil completo (resumen y muestras) en un archivo
+        #[arg(short, long)]
+        output: Option<String>,
 
-        /// Umbral de memoria para activar la alerta
+        /// Guarda las muestras en la base de datos
         #[arg(long)]
-        memory_over: Option<u64>,
+        save: bool,
+
+        /// Comando a ejecutar, tras `--`
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
 
-        /// Acción a realizar cuando se exceden los umbrales
+    /// Ejecuta un comando varias veces y resume tiempo, CPU y memoria
+    Bench {
+        /// Ejecuciones medidas
+        #[arg(short, long, default_value_t = 10)]
+        runs: usize,
+
+        /// Ejecuciones previas que no se miden
+        #[arg(short, long, default_value_t = 0)]
+        warmup: usize,
+
+        /// Intervalo entre muestras de cada ejecución (milisegundos)
+        #[arg(short = 'i', long, default_value_t = 50)]
+        interval_ms: u64,
+
+        /// Segundo comando con el que comparar (entre comillas)
         #[arg(long)]
-        on_exceed: Option<String>,
+        compare: Option<String>,
 
-        /// Intervalo de tiempo para verificar los umbrales (en milisegundos)
-        #[arg(long, default_value_t = 1000)]
-        interval: u64,
+        /// Muestra la salida del comando (por defecto se descarta)
+        #[arg(long)]
+        show_output: bool,
+
+        /// Formato de salida (text, json, csv)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Archivo de salida (opcional)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Comando a ejecutar, tras `--`
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
+
+    /// Genera logs y reportes de auditoría
+    Watchdog(WatchdogArgs),
+
+    /// Alertas registradas por el watchdog
+    Alerts {
+        #[command(subcommand)]
+        action: AlertsAction,
     },
 
     /// Etiquetado de procesos
@@ -176,8 +377,24 @@ pub enum Commands {
     /// Exploracion de procesos por familia
     Family {
         /// ID del proceso (PID)
+        #[arg(short, long, required_unless_present = "all")]
+        pid: Option<u32>,
+
+        /// Muestra los ancestros del proceso hasta la raíz en lugar de sus descendientes
+        #[arg(long, requires = "pid")]
+        ancestors: bool,
+
+        /// Muestra el árbol de todo el sistema
+        #[arg(long, conflicts_with_all = ["pid", "ancestors"])]
+        all: bool,
+
+        /// Niveles a mostrar (hacia abajo, o hacia arriba con --ancestors)
         #[arg(short, long)]
-        pid: u32,
+        depth: Option<usize>,
+
+        /// Agrupa hermanos idénticos (p. ej. 12×[php-fpm])
+        #[arg(short, long)]
+        collapse: bool,
 
         /// Formato de salida (text, json, csv)
         #[arg(short, long, default_value = "text")]
@@ -202,4 +419,140 @@ pub enum ConfigAction {
     },
     /// Resetear configuración a valores por defecto
     Reset,
+    /// Enviar una alerta de prueba a un canal de notificación
+    TestNotifier {
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AlertsAction {
+    /// Listar alertas, de la más reciente a la más antigua
+    List {
+        /// Filtrar por regla
+        #[arg(long)]
+        rule: Option<String>,
+
+        /// Filtrar por PID
+        #[arg(short, long)]
+        pid: Option<u32>,
+
+        /// Solo alertas cuya condición sigue activa
+        #[arg(long)]
+        active: bool,
+
+        /// Solo alertas sin reconocer
+        #[arg(long)]
+        unacked: bool,
+
+        /// Límite de registros
+        #[arg(short, long, default_value_t = 50)]
+        limit: i64,
+
+        /// Formato de salida (text, json, csv)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Archivo de salida (opcional)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Mostrar el detalle de una alerta
+    Show {
+        id: i32,
+
+        /// Formato de salida (text, json, csv)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+    /// Reconocer una alerta
+    Ack {
+        id: i32,
+
+        /// Nota asociada al reconocimiento
+        #[arg(long)]
+        note: Option<String>,
+    },
+}
+
+#[derive(Args)]
+pub struct WatchdogArgs {
+    /// ID del proceso a monitorear (PID). Las reglas sin `pid` ni `process` se aplican a él
+    #[arg(short, long)]
+    pub pid: Option<u32>,
+
+    /// Archivo TOML con el conjunto de reglas (se recarga con SIGHUP)
+    #[arg(long)]
+    pub rules: Option<PathBuf>,
+
+    /// Umbral de CPU para activar la alerta
+    #[arg(long)]
+    pub cpu_over: Option<f32>,
+
+    /// Umbral de memoria para activar la alerta
+    #[arg(long)]
+    pub memory_over: Option<u64>,
+
+    /// Condición adicional (`threads > 200`, `read > 10M`, `state == zombie`, `spawn_rate > 30`, `anomalous(cpu)`...).
+    /// Puede repetirse
+    #[arg(long)]
+    pub condition: Vec<String>,
+
+    /// Acción a realizar cuando se exceden los umbrales (kill, renice:<nice>, log, export, exec:<comando>, notify:<canal>).
+    /// Puede repetirse para encadenar varias acciones
+    #[arg(long)]
+    pub on_exceed: Vec<String>,
+
+    /// Paso de escalado `<tiempo>:<acción>` (p. ej. `1m:notify:oncall`, `5m:renice:10`, `10m:kill`).
+    /// Se ejecuta una vez si la condición sigue activa pasado ese tiempo; puede repetirse
+    #[arg(long)]
+    pub escalate: Vec<String>,
+
+    /// Nombre de la regla (se pasa como contexto a las acciones)
+    #[arg(long, default_value = "cli")]
+    pub rule: String,
+
+    /// Tiempo máximo de ejecución de un hook exec (en segundos)
+    #[arg(long, default_value_t = 30)]
+    pub exec_timeout: u64,
+
+    /// Intervalo de tiempo para verificar los umbrales (en milisegundos)
+    #[arg(long, default_value_t = 1000)]
+    pub interval: u64,
+
+    /// Reproducir las reglas sobre muestras guardadas sin ejecutar ninguna acción
+    #[arg(long, conflicts_with = "daemon")]
+    pub backtest: bool,
+
+    /// Inicio del backtest (fecha local: `2024-05-01`, `2024-05-01 13:00` o RFC 3339)
+    #[arg(long, requires = "backtest")]
+    pub from: Option<String>,
+
+    /// Fin del backtest (mismo formato que --from)
+    #[arg(long, requires = "backtest")]
+    pub to: Option<String>,
+
+    /// Snapshots JSON a usar en el backtest en lugar de la base de datos
+    #[arg(long, num_args = 1.., requires = "backtest")]
+    pub snapshots: Vec<PathBuf>,
+
+    /// Formato del reporte de backtest (text, json, csv)
+    #[arg(short, long, default_value = "text")]
+    pub format: String,
+
+    /// No registrar las alertas en la base de datos
+    #[arg(long)]
+    pub no_store: bool,
+
+    /// Ejecutar en segundo plano como daemon (solo Unix)
+    #[arg(long)]
+    pub daemon: bool,
+
+    /// Pidfile del daemon
+    #[arg(long, requires = "daemon")]
+    pub pidfile: Option<PathBuf>,
+
+    /// Archivo de log del daemon
+    #[arg(long, requires = "daemon")]
+    pub log_file: Option<PathBuf>,
 }
@@ -1,21 +1,33 @@
 use crate::cli::{Commands};
 use crate::handlers::tui::handle_tui;
-use crate::output::OutputFormatter;
 use crate::error::{CliResult};
 use crate::config::Config;
+use argos_core::commands::bench::BenchOptions;
+use argos_core::commands::compare::matching::Tolerance;
+use argos_core::commands::family::{FamilyOptions, FamilyView};
+use argos_core::commands::live::LiveTargets;
+use argos_core::commands::sampling::SampleTargets;
+use argos_core::commands::top::TopOptions;
+use std::time::Duration;
 use crate::handlers::{list::handle_list,
                      monitor::handle_monitor,
                      sample::handle_sample,
                      live::handle_live,
-                     compare::handle_compare,
+                     compare::{handle_compare, handle_compare_sessions, CompareGate, CompareOptions},
                      watchdog::handle_watchdog,
                      config::handle_config,
-                     family::handle_family};
+                     family::handle_family,
+                     alerts::handle_alerts,
+                     snapshot::handle_snapshot,
+                     run::{handle_run, RunOptions},
+                     bench::handle_bench,
+                     top::handle_top,
+                     zombies::handle_zombies,
+                     leak::{handle_leakcheck, LeakCheckArgs}};
 
                      
 #[derive(Debug)]
 pub struct CommandHandler {
-    formatter: OutputFormatter,
     config: Config,
 }
 
@@ -23,7 +35,6 @@ impl CommandHandler {
     pub fn new() -> Self {
         let config = Config::load().unwrap_or_default();
         Self {
-            formatter: OutputFormatter::new(),
             config,
         }
     }
@@ -36,28 +47,87 @@ impl CommandHandler {
             Commands::Monitor { pid, format, save } => {
                 handle_monitor(pid, &format, save)
             }
-            Commands::Sample { pid, iterations, interval_ms, format, output } => {
-                handle_sample(pid, iterations, interval_ms, &format, output.as_deref())
+            Commands::Sample { pid, name, include_children, iterations, interval_ms, format, output, save } => {
+                let targets = SampleTargets { pids: pid, names: name, include_children };
+                handle_sample(&self.config, &targets, iterations, interval_ms, &format, output.as_deref(), save)
             }
             Commands::History { pid, limit, format } => {
-                print!("History command selected with pid: {:?}, limit: {}, format: {}\n", pid, limit, format);
+                println!("History command selected with pid: {:?}, limit: {}, format: {}", pid, limit, format);
                 Ok(())
             }
-            Commands::Live {pid, output, format} => {
-                handle_live(pid, output.as_deref(), format.as_deref())
+            Commands::Live { pid, name, follow_children, interval_ms, output, format } => {
+                let targets = LiveTargets { pids: pid, names: name, follow_children };
+                handle_live(&targets, interval_ms, output.as_deref(), format.as_deref())
             }
-            Commands::Compare {pids, files, format, output, interval} => {
-                handle_compare(pids, files, &format, output.as_deref(), interval)
+            Commands::Compare {sessions: Some(sessions), format, output, confidence, ..} => {
+                handle_compare_sessions(&self.config, &sessions, &format, output.as_deref(), confidence)
             }
-            Commands::Watchdog { pid, cpu_over, memory_over, on_exceed, interval } => {
-                handle_watchdog(pid, cpu_over, memory_over, on_exceed, interval)
+            Commands::Compare {
+                pids, files, format, output, interval, match_by, only, cpu_tolerance, mem_tolerance, fail_if, junit, ..
+            } => {
+                let options = CompareOptions {
+                    interval_ms: interval,
+                    match_by,
+                    tolerance: Tolerance { cpu: cpu_tolerance, memory_mb: mem_tolerance },
+                    only,
+                };
+                let gate = CompareGate { thresholds: fail_if, junit };
+                handle_compare(pids, files, &format, output.as_deref(), &options, &gate)
+            }
+            Commands::Snapshot { output, gzip } => {
+                handle_snapshot(&output, gzip)
+            }
+            Commands::Run { interval_ms, format, output, save, command } => {
+                let options = RunOptions { interval_ms, format: &format, output: output.as_deref(), save };
+                handle_run(&self.config, &command, &options)
+            }
+            Commands::Bench { runs, warmup, interval_ms, compare, show_output, format, output, command } => {
+                let options = BenchOptions { runs, warmup, interval_ms, show_output };
+                handle_bench(&command, compare.as_deref(), &options, &format, output.as_deref())
+            }
+            Commands::Top { top, sort_by, filter, iterations, interval_ms, batch, format } => {
+                let options = TopOptions {
+                    limit: top,
+                    sort_by,
+                    filters: filter,
+                    interval: Duration::from_millis(interval_ms),
+                    iterations,
+                };
+                handle_top(&options, &format, batch)
+            }
+            Commands::Zombies { format, output } => {
+                handle_zombies(&format, output.as_deref())
+            }
+            Commands::Leakcheck { pid, duration, warmup, interval_ms, metric, limit, strict, format, output } => {
+                handle_leakcheck(&LeakCheckArgs {
+                    pid,
+                    duration: &duration,
+                    warmup: warmup.as_deref(),
+                    interval_ms,
+                    metric,
+                    limit: limit.as_deref(),
+                    strict,
+                    format: &format,
+                    output: output.as_deref(),
+                })
+            }
+            Commands::Watchdog(args) => {
+                handle_watchdog(&self.config, args)
+            }
+            Commands::Alerts { action } => {
+                handle_alerts(&self.config, action)
             }
             Commands::Tag { name, pid } => {
-                print!("Tag command selected with name: {}, pid: {}\n", name, pid);
+                println!("Tag command selected with name: {}, pid: {}", name, pid);
                 Ok(())
             }
-            Commands::Family { pid, format } => {
-                handle_family(pid, &format)
+            Commands::Family { pid, ancestors, all, depth, collapse, format } => {
+                let view = match pid {
+                    Some(pid) if ancestors => FamilyView::Ancestors(pid),
+                    Some(pid) if !all => FamilyView::Descendants(pid),
+                    _ => FamilyView::All,
+                };
+                handle_family(&FamilyOptions { view, depth, collapse }, &format)
             }
             Commands::Tui {} => {
                 handle_tui()
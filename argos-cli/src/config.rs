@@ -1,4 +1,6 @@
+use argos_core::notify::NotifierConfig;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::fs;
 use crate::error::{CliResult, CliError};
@@ -11,6 +13,9 @@ pub struct Config {
     pub auto_save: bool,
     pub database_url: Option<String>,
     pub log_level: String,
+    /// Canales de notificación del watchdog, referenciados como `notify:<nombre>`
+    #[serde(default)]
+    pub notifiers: HashMap<String, NotifierConfig>,
 }
 
 impl Default for Config {
@@ -22,6 +27,7 @@ impl Default for Config {
             auto_save: false,
             database_url: None,
             log_level: "info".to_string(),
+            notifiers: HashMap::new(),
         }
     }
 }
@@ -114,6 +120,27 @@ impl Config {
         Ok(())
     }
 
+    /// Base de datos a usar: `database_url`, `DATABASE_URL` o `argos.db` en el
+    /// directorio de datos del usuario.
+    pub fn database_path(&self) -> CliResult<String> {
+        if let Some(url) = &self.database_url {
+            return Ok(url.clone());
+        }
+        dotenvy::dotenv().ok();
+        if let Ok(url) = std::env::var("DATABASE_URL") {
+            return Ok(url);
+        }
+
+        let mut path = dirs::data_local_dir()
+            .ok_or_else(|| CliError::io_error("No se pudo encontrar directorio de datos"))?;
+        path.push("argos");
+        fs::create_dir_all(&path)
+            .map_err(|e| CliError::io_error(format!("Error creando directorio de datos: {}", e)))?;
+        path.push("argos.db");
+
+        Ok(path.to_string_lossy().into_owned())
+    }
+
     fn config_path() -> CliResult<PathBuf> {
         let mut path = dirs::config_dir()
             .ok_or_else(|| CliError::io_error("No se pudo encontrar directorio de configuración"))?;
@@ -125,6 +152,10 @@ impl Config {
     }
 
     pub fn display(&self) -> String {
+        let mut notifiers: Vec<&str> = self.notifiers.keys().map(|k| k.as_str()).collect();
+        notifiers.sort();
+        let notifiers = if notifiers.is_empty() { "-".to_string() } else { notifiers.join(", ") };
+
         format!(
         "⚙️  Configuración de Argos\n\
          ┌───────────────────────────────────────────┐\n\
@@ -133,12 +164,14 @@ impl Config {
         {:8}Iteraciones por defecto: {:<20}\n\
         {:8}Auto-guardar: {:<28}\n\
         {:8}Log level: {:<31}\n\
+        {:8}Notificadores: {:<27}\n\
          └───────────────────────────────────────────┘",
         "", self.default_format,
         "", format!("{} ms", self.default_interval_ms),
         "", self.default_iterations,
         "", if self.auto_save { "Sí" } else { "No" },
         "", self.log_level,
+        "", notifiers,
     )
     }
 }
@@ -0,0 +1,100 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::error::{CliError, CliResult};
+
+/// Señales que controlan un proceso de larga duración:
+/// Ctrl-C / SIGTERM piden terminar y SIGHUP pide recargar la configuración.
+pub struct Signals {
+    stop: Arc<AtomicBool>,
+    reload: Arc<AtomicBool>,
+}
+
+impl Signals {
+    pub fn install() -> CliResult<Self> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let reload = Arc::new(AtomicBool::new(false));
+
+        let flag = Arc::clone(&stop);
+        ctrlc::set_handler(move || flag.store(true, Ordering::SeqCst))
+            .map_err(|e| CliError::io_error(format!("No se pudo instalar el manejador de Ctrl-C: {}", e)))?;
+
+        #[cfg(unix)]
+        {
+            use signal_hook::consts::{SIGHUP, SIGTERM};
+            signal_hook::flag::register(SIGTERM, Arc::clone(&stop))?;
+            signal_hook::flag::register(SIGHUP, Arc::clone(&reload))?;
+        }
+
+        Ok(Self { stop, reload })
+    }
+
+    pub fn should_stop(&self) -> bool {
+        self.stop.load(Ordering::SeqCst)
+    }
+
+    /// Devuelve true una sola vez por cada recarga solicitada.
+    pub fn take_reload(&self) -> bool {
+        self.reload.swap(false, Ordering::SeqCst)
+    }
+
+    /// Duerme hasta `duration`, despertando antes si llega una señal.
+    pub fn sleep(&self, duration: Duration) {
+        let deadline = Instant::now() + duration;
+        while Instant::now() < deadline {
+            if self.should_stop() || self.reload.load(Ordering::SeqCst) {
+                return;
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            std::thread::sleep(remaining.min(Duration::from_millis(100)));
+        }
+    }
+}
+
+pub fn default_pidfile(name: &str) -> PathBuf {
+    dirs::runtime_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join(format!("argos-{}.pid", name))
+}
+
+pub fn default_log_file(name: &str) -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("argos")
+        .join(format!("{}.log", name))
+}
+
+/// Se separa de la terminal: escribe el pidfile y redirige stdout/stderr al log.
+/// Debe llamarse antes de crear hilos (manejadores de señales, sysinfo).
+#[cfg(unix)]
+pub fn detach(pidfile: &Path, log_file: &Path) -> CliResult<()> {
+    if let Some(parent) = log_file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let log = std::fs::OpenOptions::new().create(true).append(true).open(log_file)?;
+    let log_err = log.try_clone()?;
+
+    daemonize::Daemonize::new()
+        .pid_file(pidfile)
+        .working_directory(std::env::current_dir()?)
+        .stdout(log)
+        .stderr(log_err)
+        .start()
+        .map_err(|e| CliError::io_error(format!("No se pudo iniciar el daemon: {}", e)))
+}
+
+#[cfg(not(unix))]
+pub fn detach(_pidfile: &Path, _log_file: &Path) -> CliResult<()> {
+    Err(CliError::new(
+        crate::error::ErrorKind::ValidationError,
+        "El modo daemon solo está disponible en sistemas Unix",
+    ))
+}
+
+pub fn remove_pidfile(pidfile: &Path) {
+    if let Err(e) = std::fs::remove_file(pidfile) {
+        eprintln!("No se pudo eliminar el pidfile {}: {}", pidfile.display(), e);
+    }
+}
@@ -10,13 +10,12 @@ pub struct CliError {
 
 #[derive(Debug)]
 pub enum ErrorKind {
-    ProcessNotFound,
     DatabaseError,
     FormatError,
-    ConfigError,
     IoError,
     ValidationError,
     CoreError,
+    ThresholdExceeded,
 }
 
 impl CliError {
@@ -27,13 +26,6 @@ impl CliError {
         }
     }
 
-    pub fn process_not_found(pid: u32) -> Self {
-        Self::new(
-            ErrorKind::ProcessNotFound,
-            format!("No se encontró el proceso con PID {}", pid),
-        )
-    }
-
     pub fn database_error(msg: impl Into<String>) -> Self {
         Self::new(ErrorKind::DatabaseError, msg)
     }
@@ -49,19 +41,27 @@ impl CliError {
     pub fn core_error<E: std::fmt::Display>(err: E) -> Self {
         Self::new(ErrorKind::CoreError, err.to_string())
     }
+
+    /// Código de salida del proceso: 2 si falla una comprobación de umbrales
+    /// (para distinguir una regresión de un error de la herramienta), 1 en otro caso.
+    pub fn exit_code(&self) -> i32 {
+        match self.kind {
+            ErrorKind::ThresholdExceeded => 2,
+            _ => 1,
+        }
+    }
 }
 
 
 impl fmt::Display for CliError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.kind {
-            ErrorKind::ProcessNotFound => write!(f, "Proceso no encontrado: {}", self.message),
             ErrorKind::DatabaseError => write!(f, "Error de base de datos: {}", self.message),
             ErrorKind::FormatError => write!(f, "Error de formato: {}", self.message),
-            ErrorKind::ConfigError => write!(f, "Error de configuración: {}", self.message),
             ErrorKind::IoError => write!(f, "Error de E/S: {}", self.message),
             ErrorKind::ValidationError => write!(f, "Error de validación: {}", self.message),
             ErrorKind::CoreError => write!(f, "Error interno: {}", self.message),
+            ErrorKind::ThresholdExceeded => write!(f, "Umbral superado: {}", self.message),
         }
     }
 }
@@ -0,0 +1,47 @@
+use std::fs;
+
+use argos_core::commands::alerts::{ack_alert, get_alert, list_alerts};
+use argos_core::db::alert::AlertFilter;
+
+use crate::cli::AlertsAction;
+use crate::config::Config;
+use crate::error::{CliError, CliResult};
+use crate::output::OutputFormatter;
+
+pub fn handle_alerts(config: &Config, action: AlertsAction) -> CliResult<()> {
+    let database_url = config.database_path()?;
+    let formatter = OutputFormatter::new();
+
+    match action {
+        AlertsAction::List { rule, pid, active, unacked, limit, format, output } => {
+            let filter = AlertFilter {
+                rule,
+                pid: pid.map(|p| p as i32),
+                active_only: active,
+                unacked_only: unacked,
+                limit,
+            };
+            let alerts = list_alerts(Some(&database_url), &filter)
+                .map_err(|e| CliError::database_error(e.to_string()))?;
+            let formatted = formatter.format_alerts(&alerts, &format)?;
+
+            if let Some(path) = output {
+                fs::write(&path, &formatted)
+                    .map_err(|e| CliError::io_error(format!("Error al escribir archivo: {}", e)))?;
+                println!("✅ Resultados guardados en: {}", path);
+            } else {
+                println!("{}", formatted);
+            }
+        }
+        AlertsAction::Show { id, format } => {
+            let alert = get_alert(Some(&database_url), id).map_err(CliError::core_error)?;
+            println!("{}", formatter.format_alert(&alert, &format)?);
+        }
+        AlertsAction::Ack { id, note } => {
+            let alert = ack_alert(Some(&database_url), id, note.as_deref()).map_err(CliError::core_error)?;
+            println!("✅ Alerta {} reconocida ({} / PID {})", alert.id, alert.rule, alert.pid);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use std::fs;
+
+use argos_core::commands::bench::{bench_command, compare_results, split_command, BenchOptions, BenchReport};
+
+use crate::{error::{CliError, CliResult}, output::OutputFormatter};
+
+pub fn handle_bench(
+    command: &[String],
+    compare: Option<&str>,
+    options: &BenchOptions,
+    format: &str,
+    output: Option<&str>,
+) -> CliResult<()> {
+    let mut commands = vec![command.to_vec()];
+    if let Some(other) = compare {
+        let other = split_command(other);
+        if other.is_empty() {
+            return Err(CliError::format_error("--compare necesita un comando"));
+        }
+        commands.push(other);
+    }
+
+    // El progreso va a stderr para no mezclarse con el informe
+    let mut results = Vec::new();
+    for command in &commands {
+        let total = options.runs;
+        let result = bench_command(command, options, |run, warmup, summary| {
+            let kind = if warmup { "calentamiento" } else { "ejecución" };
+            eprintln!(
+                "{} {}/{}: {:.3} s, pico {:.1} MB",
+                kind,
+                run,
+                if warmup { options.warmup } else { total },
+                summary.wall_secs,
+                summary.peak_rss_mb
+            );
+        })
+        .map_err(CliError::core_error)?;
+        results.push(result);
+    }
+
+    let comparison = match results.as_slice() {
+        [a, b] => Some(compare_results(a, b)),
+        _ => None,
+    };
+    let report = BenchReport { results, comparison };
+    let formatted = OutputFormatter::new().format_bench(&report, format)?;
+
+    if let Some(path) = output {
+        fs::write(path, &formatted).map_err(|e| CliError::io_error(format!("Error al escribir archivo: {}", e)))?;
+        if format == "text" {
+            println!("✅ Resultados guardados en: {}", path);
+        }
+    } else {
+        println!("{}", formatted);
+    }
+    Ok(())
+}
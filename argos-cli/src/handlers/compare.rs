@@ -1,18 +1,39 @@
-use std::{fs, time::Duration, path::PathBuf};
+use std::{fs, time::Duration, path::{Path, PathBuf}};
 use std::thread::sleep;
 
 use argos_core::commands::compare::{
+    ab::{compare_sessions, load_sample_file, load_session, SampleSet},
     by_file::compare_by_file,
-    by_pid::{sample_process, compare_samples},
+    by_pid::sample_process,
+    gate::{evaluate_gate, Threshold},
+    matching::{compare_rows, MatchBy, Tolerance},
+    series::compare_series,
 };
-use crate::{error::{CliError, CliResult}, output::OutputFormatter};
+use argos_core::process::model::{ComparisonSummary, DeltaStatus, ProcessDelta};
+use argos_export::format_gate_junit;
+use crate::{config::Config, error::{CliError, CliResult, ErrorKind}, output::OutputFormatter};
+
+/// Umbrales de `--fail-if` y reporte JUnit opcional.
+pub struct CompareGate {
+    pub thresholds: Vec<Threshold>,
+    pub junit: Option<PathBuf>,
+}
+
+/// Cómo se comparan dos muestras y qué procesos se muestran.
+pub struct CompareOptions {
+    pub interval_ms: u64,
+    pub match_by: MatchBy,
+    pub tolerance: Tolerance,
+    pub only: Vec<DeltaStatus>, // vacío: todos
+}
 
 pub fn handle_compare(
     pids: Option<Vec<u32>>,
     files: Option<Vec<PathBuf>>,
     format: &str,
     output: Option<&str>,
-    interval_ms: u64,
+    options: &CompareOptions,
+    gate: &CompareGate,
 ) -> CliResult<()> {
     // Validar entrada
     if pids.is_none() && files.is_none() {
@@ -26,23 +47,36 @@ pub fn handle_compare(
         ));
     }
 
+    let formatter = OutputFormatter::new();
+
     // Obtener los datos
-    let comparison = if let Some(pids) = pids {
-        // Sampleo en vivo: dos muestras consecutivas para todos los PIDs
-        let old_sample = sample_process(&pids).map_err(CliError::core_error)?;
-        sleep(Duration::from_millis(interval_ms));
-        let new_sample = sample_process(&pids).map_err(CliError::core_error)?;
-        compare_samples(&old_sample, &new_sample)
-    } else if let Some(files) = files {
-        compare_by_file(&files).map_err(CliError::core_error)?
+    let (formatted_output, comparison) = if let Some(files) = files.as_ref().filter(|f| f.len() > 2) {
+        // Más de dos snapshots: se comparan como serie temporal y los umbrales
+        // se evalúan entre el primero y el último
+        if !options.only.is_empty() {
+            return Err(CliError::new(
+                ErrorKind::ValidationError,
+                "--only clasifica la comparación de dos muestras; con más de dos snapshots compare el primero y el último",
+            ));
+        }
+        let series = compare_series(files, options.match_by).map_err(CliError::core_error)?;
+        let comparison = if gate.thresholds.is_empty() {
+            Vec::new()
+        } else {
+            let ends = [files[0].clone(), files[files.len() - 1].clone()];
+            compare_by_file(&ends, options.match_by, options.tolerance).map_err(CliError::core_error)?
+        };
+        (formatter.format_series(&series, format)?, comparison)
     } else {
-        unreachable!(); // Ya validamos que uno de los dos es Some
+        let mut comparison = compare_pair(pids, files, options)?;
+        // Los conteos del resumen son siempre los de la comparación completa
+        let summary = ComparisonSummary::from_deltas(&comparison);
+        if !options.only.is_empty() {
+            comparison.retain(|d| options.only.contains(&d.status));
+        }
+        (formatter.format_comparison(&comparison, &summary, format)?, comparison)
     };
 
-    // Formatear salida
-    let formatter = OutputFormatter::new();
-    let formatted_output = formatter.format_comparison(&comparison, format)?;
-
     // Guardar en archivo o imprimir en stdout
     if let Some(path) = output {
         fs::write(path, &formatted_output)
@@ -54,5 +88,95 @@ pub fn handle_compare(
         println!("{}", formatted_output);
     }
 
+    if gate.thresholds.is_empty() {
+        return Ok(());
+    }
+    check_gate(&comparison, format, output.is_some(), gate)
+}
+
+/// Evalúa los umbrales, muestra el resultado y devuelve error si alguno se cumple.
+/// Si la comparación va a stdout en JSON o CSV, el resultado va a stderr para no
+/// mezclarlo con los datos.
+fn check_gate(comparison: &[ProcessDelta], format: &str, to_file: bool, gate: &CompareGate) -> CliResult<()> {
+    let report = evaluate_gate(comparison, &gate.thresholds);
+    let summary = OutputFormatter::new().format_gate(&report, "text")?;
+    if format == "text" || to_file {
+        println!("\n{}", summary);
+    } else {
+        eprintln!("{}", summary);
+    }
+
+    if let Some(path) = &gate.junit {
+        fs::write(path, format_gate_junit(&report, "argos compare"))
+            .map_err(|e| CliError::io_error(format!("Error al escribir reporte JUnit: {}", e)))?;
+    }
+
+    if report.passed {
+        Ok(())
+    } else {
+        Err(CliError::new(
+            ErrorKind::ThresholdExceeded,
+            format!("{} violaciones de los umbrales de --fail-if", report.violations().count()),
+        ))
+    }
+}
+
+/// Un archivo existente se lee como exportación de `sample`; si no, se busca
+/// la sesión con ese ID en la base de datos.
+fn load_sample_set(config: &Config, source: &str) -> CliResult<SampleSet> {
+    let path = Path::new(source);
+    if path.is_file() {
+        load_sample_file(path).map_err(CliError::core_error)
+    } else {
+        load_session(Some(&config.database_path()?), source).map_err(CliError::core_error)
+    }
+}
+
+/// Comparación estadística A/B entre dos sesiones de muestreo.
+pub fn handle_compare_sessions(
+    config: &Config,
+    sessions: &[String],
+    format: &str,
+    output: Option<&str>,
+    confidence: f64,
+) -> CliResult<()> {
+    if !(0.5..1.0).contains(&confidence) {
+        return Err(CliError::new(ErrorKind::ValidationError, "--confidence debe estar entre 0.5 y 1 (p. ej. 0.95)"));
+    }
+    let a = load_sample_set(config, &sessions[0])?;
+    let b = load_sample_set(config, &sessions[1])?;
+    let report = compare_sessions(&a, &b, confidence);
+    let formatted_output = OutputFormatter::new().format_ab(&report, format)?;
+
+    if let Some(path) = output {
+        fs::write(path, &formatted_output)
+            .map_err(|e| CliError::io_error(format!("Error al escribir archivo: {}", e)))?;
+        if format == "text" {
+            println!("✅ Resultados guardados en: {}", path);
+        }
+    } else {
+        println!("{}", formatted_output);
+    }
+
     Ok(())
 }
+
+fn compare_pair(
+    pids: Option<Vec<u32>>,
+    files: Option<Vec<PathBuf>>,
+    options: &CompareOptions,
+) -> CliResult<Vec<ProcessDelta>> {
+    let comparison = if let Some(pids) = pids {
+        // Sampleo en vivo: dos muestras consecutivas para todos los PIDs
+        let old_sample = sample_process(&pids).map_err(CliError::core_error)?;
+        sleep(Duration::from_millis(options.interval_ms));
+        let new_sample = sample_process(&pids).map_err(CliError::core_error)?;
+        compare_rows(&old_sample, &new_sample, options.match_by, options.tolerance)
+    } else if let Some(files) = files {
+        compare_by_file(&files, options.match_by, options.tolerance).map_err(CliError::core_error)?
+    } else {
+        unreachable!(); // Ya validamos que uno de los dos es Some
+    };
+
+    Ok(comparison)
+}
@@ -1,5 +1,7 @@
+use argos_core::commands::types::AlertContext;
+use argos_core::notify::NotifierRegistry;
 use crate::config::Config;
-use crate::error::{CliResult};
+use crate::error::{CliError, CliResult};
 use crate::cli::ConfigAction;
 
 pub fn handle_config(config: &mut Config, action: ConfigAction) -> CliResult<()> {
@@ -24,6 +26,24 @@ pub fn handle_config(config: &mut Config, action: ConfigAction) -> CliResult<()>
 
             println!("⚙️  Configuración reseteada a valores por defecto");
         }
+        ConfigAction::TestNotifier { name } => {
+            // Alerta ficticia para comprobar la entrega del canal
+            let registry = NotifierRegistry::from_config(&config.notifiers).map_err(CliError::core_error)?;
+            let alert = AlertContext {
+                rule: "test".to_string(),
+                pid: std::process::id(),
+                name: "argos".to_string(),
+                condition: "test".to_string(),
+                metric: "test".to_string(),
+                value: None,
+                cpu_usage: 0.0,
+                memory_bytes: 0,
+                triggered_at: chrono::Utc::now(),
+            };
+            registry.notify(&name, &alert).map_err(CliError::core_error)?;
+
+            println!("📨 Alerta de prueba enviada a '{}'", name);
+        }
     }
     Ok(())
 }
@@ -0,0 +1,96 @@
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::time::Duration;
+
+use argos_core::commands::leak::{check_leak, LeakOptions, LeakVerdict, MemoryMetric, MemorySample};
+use argos_core::commands::types::{parse_bytes, parse_duration_secs};
+
+use crate::error::{CliError, CliResult, ErrorKind};
+use crate::output::OutputFormatter;
+
+/// Opciones de `argos leakcheck` tal como llegan de la línea de comandos.
+pub struct LeakCheckArgs<'a> {
+    pub pid: u32,
+    pub duration: &'a str,
+    pub warmup: Option<&'a str>,
+    pub interval_ms: u64,
+    pub metric: MemoryMetric,
+    pub limit: Option<&'a str>,
+    pub strict: bool,
+    pub format: &'a str,
+    pub output: Option<&'a str>,
+}
+
+fn duration(value: &str) -> CliResult<Duration> {
+    parse_duration_secs(value)
+        .map(Duration::from_secs)
+        .ok_or_else(|| CliError::new(ErrorKind::ValidationError, format!("Duración inválida: {}", value)))
+}
+
+/// Muestrea la memoria del proceso durante la ventana y muestra el análisis.
+/// Termina con código 2 si hay fuga (o posible fuga con `strict`), para usarlo
+/// en pruebas de larga duración.
+pub fn handle_leakcheck(args: &LeakCheckArgs) -> CliResult<()> {
+    let total = duration(args.duration)?;
+    // Por defecto se descarta el primer 10 % de la ventana
+    let warmup = match args.warmup {
+        Some(value) => duration(value)?,
+        None => total / 10,
+    };
+    let limit_mb = args
+        .limit
+        .map(|value| {
+            parse_bytes(value)
+                .map(|bytes| bytes as f64 / 1_048_576.0)
+                .ok_or_else(|| CliError::new(ErrorKind::ValidationError, format!("Límite inválido: {}", value)))
+        })
+        .transpose()?;
+
+    let options = LeakOptions {
+        pid: args.pid,
+        duration: total,
+        interval: Duration::from_millis(args.interval_ms),
+        warmup,
+        metric: args.metric,
+        limit_mb,
+    };
+
+    // Progreso en la misma línea, solo si stderr es una terminal
+    let progress = io::stderr().is_terminal();
+    let report = check_leak(&options, |sample: &MemorySample| {
+        if progress {
+            eprint!(
+                "\r⏳ {:.0}/{} s · {} {:.2} MB{}   ",
+                sample.elapsed_secs,
+                total.as_secs(),
+                args.metric,
+                sample.memory_mb,
+                if sample.warmup { " (calentamiento)" } else { "" }
+            );
+        }
+    })
+    .map_err(CliError::core_error)?;
+    if progress {
+        eprintln!();
+    }
+
+    let output = OutputFormatter::new().format_leak(&report, args.format)?;
+    if let Some(path) = args.output {
+        fs::write(path, &output).map_err(|e| CliError::io_error(format!("Error al escribir archivo: {}", e)))?;
+        eprintln!("✅ Resultados guardados en: {}", path);
+    } else {
+        println!("{}", output.trim_end());
+    }
+
+    match report.verdict {
+        LeakVerdict::Leak => Err(CliError::new(
+            ErrorKind::ThresholdExceeded,
+            format!("{} del proceso {} crece {:+.2} MB/h", report.metric, report.pid, report.growth_mb_per_hour),
+        )),
+        LeakVerdict::PossibleLeak if args.strict => Err(CliError::new(
+            ErrorKind::ThresholdExceeded,
+            format!("posible fuga en el proceso {} ({:+.2} MB/h)", report.pid, report.growth_mb_per_hour),
+        )),
+        _ => Ok(()),
+    }
+}
@@ -1,9 +1,15 @@
 use crate::{error::{CliError, CliResult}, output::OutputFormatter};
-use argos_core::commands::live::monitor_live_by_pid;
+use argos_core::commands::live::{monitor_live, LiveTargets, LiveTick};
 use std::fs::OpenOptions;
 use std::io::Write;
+use std::time::Duration;
 
-pub fn handle_live(pid: u32, output_file: Option<&str>, format: Option<&str>) -> CliResult<()> {
+pub fn handle_live(
+    targets: &LiveTargets,
+    interval_ms: u64,
+    output_file: Option<&str>,
+    format: Option<&str>,
+) -> CliResult<()> {
     let formatter = OutputFormatter::new();
     let format = format.unwrap_or("text");
 
@@ -20,9 +26,12 @@ pub fn handle_live(pid: u32, output_file: Option<&str>, format: Option<&str>) ->
         None
     };
 
-    // Closure que se ejecuta en cada iteración
-    let mut callback = |process: &argos_core::process::model::ProcessRow| {
-        match formatter.format_process_info(process, format) {
+    // Closure que se ejecuta en cada iteración; la cabecera CSV solo va en la primera
+    let mut header = true;
+    let mut callback = |tick: &LiveTick| {
+        let formatted = formatter.format_live_tick(tick, format, header);
+        header &= tick.samples.is_empty();
+        match formatted {
             Ok(mut output) => {
                 // Elimina saltos de línea al final
                 while output.ends_with('\n') || output.ends_with(' ') {
@@ -42,5 +51,5 @@ pub fn handle_live(pid: u32, output_file: Option<&str>, format: Option<&str>) ->
     };
 
     // Llamar al core con el callback
-    monitor_live_by_pid(pid, &mut callback).map_err(CliError::core_error)
+    monitor_live(targets, Duration::from_millis(interval_ms), &mut callback).map_err(CliError::core_error)
 }
@@ -8,4 +8,11 @@ pub mod compare;
 pub mod watchdog;
 pub mod config;
 pub mod family;
-pub mod tui;
\ No newline at end of file
+pub mod tui;
+pub mod alerts;
+pub mod snapshot;
+pub mod run;
+pub mod bench;
+pub mod top;
+pub mod zombies;
+pub mod leak;
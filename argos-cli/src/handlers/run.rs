@@ -0,0 +1,44 @@
+use std::fs;
+
+use argos_core::commands::run::profile_command;
+use argos_core::commands::sampling::save_sample_run;
+
+use crate::{config::Config, error::{CliError, CliResult}, output::OutputFormatter};
+
+/// Opciones de `argos run`.
+pub struct RunOptions<'a> {
+    pub interval_ms: u64,
+    pub format: &'a str,
+    pub output: Option<&'a str>,
+    pub save: bool,
+}
+
+/// Ejecuta el comando, lo perfila hasta que termina e imprime el resumen en
+/// stderr (stdout queda para el comando). Argos termina con el mismo código
+/// que el comando, como `time`.
+pub fn handle_run(config: &Config, command: &[String], options: &RunOptions) -> CliResult<()> {
+    let profile = profile_command(command, options.interval_ms, false).map_err(CliError::core_error)?;
+    let formatter = OutputFormatter::new();
+
+    if options.save {
+        let sessions = save_sample_run(Some(&config.database_path()?), &profile.to_sample_run())
+            .map_err(CliError::core_error)?;
+        for id in sessions {
+            eprintln!("💾 Sesión guardada: {}", id);
+        }
+    }
+
+    if let Some(path) = options.output {
+        let formatted = formatter.format_run_profile(&profile, options.format)?;
+        fs::write(path, formatted).map_err(|e| CliError::io_error(format!("Error al escribir archivo: {}", e)))?;
+        eprintln!("✅ Perfil guardado en: {}", path);
+    }
+
+    eprintln!("{}", formatter.format_run_summary(&profile.summary, options.format)?.trim_end());
+
+    let status = profile.summary.exit_status();
+    if status != 0 {
+        std::process::exit(status);
+    }
+    Ok(())
+}
@@ -1,12 +1,28 @@
 use std::fs;
 
-use argos_core::commands::sampling::sample_process;
+use argos_core::commands::sampling::{sample_targets, save_sample_run, SampleTargets};
 
-use crate::{error::{CliError, CliResult}, output::OutputFormatter};
+use crate::{config::Config, error::{CliError, CliResult}, output::OutputFormatter};
 
-pub fn handle_sample(pid: u32, iterations: u32, interval_ms: u64, format: &str, output: Option<&str>) -> CliResult<()> {
+pub fn handle_sample(
+    config: &Config,
+    targets: &SampleTargets,
+    iterations: u32,
+    interval_ms: u64,
+    format: &str,
+    output: Option<&str>,
+    save: bool,
+) -> CliResult<()> {
     // Llamar al core
-    let samples = sample_process(pid, iterations as usize, interval_ms as u64).map_err(CliError::core_error)?;
+    let samples = sample_targets(targets, iterations as usize, interval_ms).map_err(CliError::core_error)?;
+
+    // Guardar en la base de datos, una sesión por proceso
+    if save {
+        let sessions = save_sample_run(Some(&config.database_path()?), &samples).map_err(CliError::core_error)?;
+        for id in sessions {
+            eprintln!("💾 Sesión guardada: {}", id);
+        }
+    }
 
     // Formatear salida
     let formatter = OutputFormatter::new();
@@ -0,0 +1,20 @@
+use std::path::Path;
+
+use argos_core::commands::snapshot::{capture_snapshot, write_snapshot};
+
+use crate::error::{CliError, CliResult};
+
+pub fn handle_snapshot(output: &Path, gzip: bool) -> CliResult<()> {
+    let gzip = gzip || output.extension().is_some_and(|e| e == "gz");
+    let snapshot = capture_snapshot().map_err(CliError::core_error)?;
+    write_snapshot(&snapshot, output, gzip)
+        .map_err(|e| CliError::io_error(format!("Error al escribir snapshot: {}", e)))?;
+
+    println!(
+        "✅ Snapshot guardado en: {} ({} procesos, {})",
+        output.display(),
+        snapshot.processes.len(),
+        snapshot.host.hostname.as_deref().unwrap_or("equipo desconocido")
+    );
+    Ok(())
+}
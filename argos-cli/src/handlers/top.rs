@@ -0,0 +1,59 @@
+use crate::{error::{CliError, CliResult}, output::OutputFormatter};
+use argos_core::commands::top::{monitor_top, TopFrame, TopOptions};
+use std::io::{self, IsTerminal, Write};
+
+/// Secuencias ANSI para redibujar en el sitio: cursor al inicio, borrar hasta
+/// el final de la línea y hasta el final de la pantalla.
+const CURSOR_HOME: &str = "\x1b[H";
+const CLEAR_LINE: &str = "\x1b[K";
+const CLEAR_BELOW: &str = "\x1b[J";
+const CLEAR_SCREEN: &str = "\x1b[2J";
+
+pub fn handle_top(options: &TopOptions, format: &str, batch: bool) -> CliResult<()> {
+    let formatter = OutputFormatter::new();
+    // Solo se redibuja la tabla de texto en una terminal; en modo batch, con la
+    // salida redirigida o en JSON/CSV cada iteración se añade debajo
+    let redraw = !batch && format == "text" && io::stdout().is_terminal();
+
+    let mut stdout = io::stdout();
+    if redraw {
+        print!("{}{}", CLEAR_SCREEN, CURSOR_HOME);
+    }
+
+    let mut header = true;
+    let mut failed = None;
+    let mut callback = |frame: &TopFrame| {
+        let output = match formatter.format_top_frame(frame, format, header) {
+            Ok(output) => output,
+            Err(e) => {
+                failed = Some(e);
+                return false;
+            }
+        };
+        header = false;
+
+        let written = if redraw {
+            // Cada línea se sobrescribe y se borra lo que quedara de la iteración anterior
+            let lines: String = output.trim_end().lines().map(|l| format!("{}{}\n", l, CLEAR_LINE)).collect();
+            write!(stdout, "{}{}{}", CURSOR_HOME, lines, CLEAR_BELOW)
+        } else if format == "text" {
+            writeln!(stdout, "{}", output)
+        } else {
+            writeln!(stdout, "{}", output.trim_end())
+        };
+        // Con la salida cerrada (p. ej. `argos top | head`) no tiene sentido seguir
+        if let Err(e) = written.and_then(|_| stdout.flush()) {
+            if e.kind() != io::ErrorKind::BrokenPipe {
+                failed = Some(CliError::io_error(format!("Error al escribir la salida: {}", e)));
+            }
+            return false;
+        }
+        true
+    };
+
+    monitor_top(options, &mut callback).map_err(CliError::core_error)?;
+    match failed {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
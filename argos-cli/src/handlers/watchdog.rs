@@ -1,42 +1,286 @@
-use argos_core::commands::watchdog::watchdog;
-use argos_core::commands::types::{Condition, Action};
-use crate::error::{CliError, CliResult};
+use argos_core::anomaly::{AnomalyEvent, AnomalyMetric};
+use argos_core::commands::watchdog::backtest::{backtest, load_db_samples, load_snapshot_samples};
+use argos_core::commands::watchdog::engine::{validate_rules, Watchdog};
+use argos_core::commands::watchdog::rules::load_rules;
+use argos_core::commands::watchdog::WatchdogReport;
+use argos_core::commands::types::{parse_duration_secs, Condition, Action, EscalationStep, Rule, Target};
+use argos_core::notify::NotifierRegistry;
+use crate::cli::WatchdogArgs;
+use crate::config::Config;
+use crate::daemon::{self, Signals};
+use crate::error::{CliError, CliResult, ErrorKind};
+use crate::output::OutputFormatter;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use std::path::Path;
 use std::time::Duration;
 
-pub fn handle_watchdog(
-    pid: u32,
-    cpu_over: Option<f32>,
-    memory_over: Option<u64>,
-    on_exceed: Option<String>, // <- Opción CLI
-    interval: u64,
-) -> CliResult<()> {
-    let action: Option<Action> = on_exceed
-        .map(|s| s.parse())
-        .transpose()
-        .map_err(|_| CliError::io_error("Invalid action".to_string()))?;
+fn parse_action(value: &str, exec_timeout: u64) -> CliResult<Action> {
+    match value.parse::<Action>().map_err(|e| CliError::new(ErrorKind::ValidationError, e))? {
+        Action::Exec { command, .. } => Ok(Action::Exec { command, timeout: Duration::from_secs(exec_timeout) }),
+        other => Ok(other),
+    }
+}
+
+/// Pasos de `--escalate <tiempo>:<acción>`, agrupados y ordenados por tiempo.
+fn cli_escalation(args: &WatchdogArgs) -> CliResult<Vec<EscalationStep>> {
+    let mut steps: Vec<EscalationStep> = Vec::new();
+    for value in &args.escalate {
+        let invalid = || CliError::new(ErrorKind::ValidationError, format!("Escalado inválido (use <tiempo>:<acción>): {}", value));
+        let (after, action) = value.split_once(':').ok_or_else(invalid)?;
+        let after = Duration::from_secs(parse_duration_secs(after).ok_or_else(invalid)?);
+        let action = parse_action(action, args.exec_timeout)?;
+
+        match steps.iter_mut().find(|s| s.after == after) {
+            Some(step) => step.actions.push(action),
+            None => steps.push(EscalationStep { after, actions: vec![action] }),
+        }
+    }
+    steps.sort_by_key(|s| s.after);
+    Ok(steps)
+}
+
+/// Regla construida a partir de los umbrales pasados por línea de comandos.
+fn cli_rule(args: &WatchdogArgs) -> CliResult<Option<Rule>> {
+    let actions = args
+        .on_exceed
+        .iter()
+        .map(|s| parse_action(s, args.exec_timeout))
+        .collect::<CliResult<Vec<_>>>()?;
 
     let mut conditions = Vec::new();
-    if let Some(cpu) = cpu_over {
+    if let Some(cpu) = args.cpu_over {
         conditions.push(Condition::CpuAbove(cpu));
     }
-    if let Some(mem) = memory_over {
+    if let Some(mem) = args.memory_over {
         conditions.push(Condition::MemAbove(mem));
     }
+    for condition in &args.condition {
+        conditions.push(
+            condition
+                .parse::<Condition>()
+                .map_err(|e| CliError::new(ErrorKind::ValidationError, e))?,
+        );
+    }
 
-    let duration = Duration::from_secs(interval);
+    if conditions.is_empty() {
+        return Ok(None);
+    }
 
-    let report = watchdog(
-        pid,
-        duration,
+    Ok(Some(Rule {
+        name: args.rule.clone(),
+        target: None,
         conditions,
-        action.into_iter().collect(), // convierte Option<Action> a Vec<Action>
-    ).map_err(CliError::core_error)?;
+        actions,
+        escalation: cli_escalation(args)?,
+    }))
+}
 
-    println!("Watchdog finished for PID {} after {:?}", report.pid, report.duration);
-    for (cond, act) in report.triggered {
-        println!("Triggered {:?} -> {:?}", cond, act);
+/// Reglas del archivo (si lo hay) más la regla de la línea de comandos.
+/// Las reglas sin objetivo se aplican a `--pid`.
+fn build_rules(args: &WatchdogArgs, rules_path: Option<&Path>) -> CliResult<Vec<Rule>> {
+    let mut rules = match rules_path {
+        Some(path) => load_rules(path).map_err(CliError::core_error)?,
+        None => Vec::new(),
+    };
+    rules.extend(cli_rule(args)?);
+
+    if rules.is_empty() {
+        return Err(CliError::new(
+            ErrorKind::ValidationError,
+            "Indique umbrales (--cpu-over, --memory-over, --condition) o un archivo de reglas (--rules)",
+        ));
+    }
+
+    for rule in &mut rules {
+        if rule.target.is_none() {
+            let pid = args.pid.ok_or_else(|| {
+                CliError::new(
+                    ErrorKind::ValidationError,
+                    format!("La regla '{}' no indica `pid` ni `process`; use --pid", rule.name),
+                )
+            })?;
+            rule.target = Some(Target::Pid(pid));
+        }
+    }
+
+    Ok(rules)
+}
+
+fn log_line(message: impl AsRef<str>) {
+    println!("{} {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"), message.as_ref());
+}
+
+fn metric_value(metric: AnomalyMetric, value: f64) -> String {
+    match metric {
+        AnomalyMetric::Cpu => format!("{:.1}%", value),
+        AnomalyMetric::Memory => format!("{:.1} MB", value / 1_048_576.0),
+        AnomalyMetric::DiskRead | AnomalyMetric::DiskWrite => format!("{:.1} KB/s", value / 1024.0),
+        AnomalyMetric::OpenFds | AnomalyMetric::Threads => format!("{:.0}", value),
+    }
+}
+
+fn describe_anomaly(event: &AnomalyEvent) -> String {
+    let anomaly = &event.anomaly;
+    format!(
+        "Anomalía ({}) en {} de {} (PID {}): {} frente a {} habitual · puntuación {:.1}, gravedad {}",
+        anomaly.kind,
+        anomaly.metric,
+        event.name,
+        event.pid,
+        metric_value(anomaly.metric, anomaly.value),
+        metric_value(anomaly.metric, anomaly.expected),
+        anomaly.score,
+        anomaly.severity
+    )
+}
+
+/// Interpreta una fecha local (`2024-05-01`, `2024-05-01 13:00[:00]`) o RFC 3339.
+fn parse_time(value: &str) -> CliResult<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M"))
+        .or_else(|_| NaiveDate::parse_from_str(value, "%Y-%m-%d").map(|d| d.and_hms_opt(0, 0, 0).unwrap()))
+        .map_err(|_| CliError::new(ErrorKind::ValidationError, format!("Fecha inválida: {}", value)))?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+        .ok_or_else(|| CliError::new(ErrorKind::ValidationError, format!("Fecha inexistente: {}", value)))
+}
+
+/// Reproduce las reglas sobre la historia guardada y muestra cuándo se habrían disparado.
+fn handle_backtest(config: &Config, args: &WatchdogArgs, rules: Vec<Rule>) -> CliResult<()> {
+    let from = args.from.as_deref().map(parse_time).transpose()?;
+    let to = args.to.as_deref().map(parse_time).transpose()?;
+
+    let samples = if args.snapshots.is_empty() {
+        load_db_samples(Some(&config.database_path()?), from, to)
+            .map_err(|e| CliError::database_error(e.to_string()))?
+    } else {
+        load_snapshot_samples(&args.snapshots, from, to).map_err(CliError::core_error)?
+    };
+
+    let mut report = backtest(&rules, samples);
+    // Con rango explícito el reporte refleja lo pedido, aunque no haya muestras en los extremos
+    report.from = from.or(report.from);
+    report.to = to.or(report.to);
+
+    println!("{}", OutputFormatter::new().format_backtest(&report, &args.format)?);
+    Ok(())
+}
+
+pub fn handle_watchdog(config: &Config, args: WatchdogArgs) -> CliResult<()> {
+    // Rutas absolutas: el daemon puede cambiar de directorio
+    let rules_path = args.rules.as_deref().map(std::fs::canonicalize).transpose()?;
+    let rules = build_rules(&args, rules_path.as_deref())?;
+    let notifiers = NotifierRegistry::from_config(&config.notifiers).map_err(CliError::core_error)?;
+    validate_rules(&rules, &notifiers).map_err(CliError::core_error)?;
+
+    if args.backtest {
+        return handle_backtest(config, &args, rules);
+    }
+
+    let pidfile = args.pidfile.clone().unwrap_or_else(|| daemon::default_pidfile("watchdog"));
+    if args.daemon {
+        let log_file = args.log_file.clone().unwrap_or_else(|| daemon::default_log_file("watchdog"));
+        println!("Watchdog en segundo plano (pidfile: {}, log: {})", pidfile.display(), log_file.display());
+        daemon::detach(&pidfile, &log_file)?;
+        log_line(format!("Watchdog iniciado (PID {})", std::process::id()));
+    }
+
+    let signals = Signals::install()?;
+    let mut engine = Watchdog::new(rules, &notifiers).map_err(CliError::core_error)?;
+    if !args.no_store {
+        engine
+            .persist_alerts(Some(&config.database_path()?))
+            .map_err(|e| CliError::database_error(e.to_string()))?;
     }
+    let interval = Duration::from_millis(args.interval);
+    let mut had_targets = true;
+
+    while !signals.should_stop() {
+        if signals.take_reload() {
+            // Una recarga inválida conserva las reglas anteriores
+            match build_rules(&args, rules_path.as_deref())
+                .and_then(|rules| engine.reload(rules).map_err(CliError::core_error))
+            {
+                Ok(()) => log_line(format!("Reglas recargadas ({} reglas)", engine.rules().len())),
+                Err(e) => log_line(format!("Recarga fallida, se mantienen las reglas anteriores: {}", e)),
+            }
+        }
+
+        match engine.tick() {
+            Ok(fired) => {
+                for event in engine.anomalies() {
+                    log_line(describe_anomaly(event));
+                }
+                for (alert, action) in fired {
+                    log_line(format!(
+                        "[{}] {} (PID {}) {} -> {:?}",
+                        alert.rule, alert.name, alert.pid, alert.condition, action
+                    ));
+                }
+            }
+            // En segundo plano un fallo puntual no debe tumbar el daemon
+            Err(e) if args.daemon => log_line(format!("Error: {}", e)),
+            // En primer plano se sale, pero sin dejar abiertas las alertas de la base de datos
+            Err(e) => {
+                if let Err(close) = engine.finish() {
+                    log_line(format!("No se pudieron cerrar las alertas abiertas: {}", close));
+                }
+                return Err(CliError::core_error(e));
+            }
+        }
+
+        let has_targets = engine.has_live_targets();
+        if !has_targets {
+            if !args.daemon {
+                break;
+            }
+            if had_targets {
+                log_line("Los procesos vigilados terminaron; esperando recarga de reglas (SIGHUP)");
+            }
+        }
+        had_targets = has_targets;
+
+        signals.sleep(interval);
+    }
+
+    // Las alertas que siguen abiertas se cierran al detener el watchdog
+    let report = engine.finish().map_err(|e| CliError::database_error(e.to_string()))?;
+    if args.daemon {
+        log_line("Watchdog detenido");
+        daemon::remove_pidfile(&pidfile);
+    }
+    print_report(&report);
 
     Ok(())
 }
 
+fn print_report(report: &WatchdogReport) {
+    println!("Watchdog finished after {:?}", report.duration);
+    for (cond, act) in &report.triggered {
+        println!("Triggered {:?} -> {:?}", cond, act);
+    }
+    for result in &report.exec_results {
+        let status = if let Some(e) = &result.error {
+            format!("fallida ({})", e)
+        } else if result.timed_out {
+            "timeout".to_string()
+        } else {
+            result.exit_code.map_or("signal".to_string(), |c| format!("exit {}", c))
+        };
+        println!("Exec [{}] '{}' -> {} ({} ms)", result.rule, result.command, status, result.duration_ms);
+    }
+    for result in &report.notifications {
+        match &result.error {
+            None => println!("Notify [{}] -> {}: entregada", result.rule, result.channel),
+            Some(e) => println!("Notify [{}] -> {}: fallida ({})", result.rule, result.channel, e),
+        }
+    }
+    if report.anomaly_count > 0 {
+        println!("Anomalías detectadas: {}", report.anomaly_count);
+    }
+}
@@ -0,0 +1,21 @@
+use argos_core::commands::zombies::find_zombies;
+use crate::error::{CliError, CliResult};
+use crate::output::OutputFormatter;
+use std::fs;
+
+pub fn handle_zombies(format: &str, output_file: Option<&str>) -> CliResult<()> {
+    let report = find_zombies().map_err(CliError::core_error)?;
+    let formatter = OutputFormatter::new();
+    let output = formatter.format_zombies(&report, format)?;
+
+    if let Some(path) = output_file {
+        fs::write(path, &output)
+            .map_err(|e| CliError::io_error(format!("Error al escribir archivo: {}", e)))?;
+        if format == "text" {
+            println!("✅ Resultados guardados en: {}", path);
+        }
+    } else {
+        println!("{}", output);
+    }
+    Ok(())
+}
@@ -4,9 +4,9 @@ mod output;
 mod config;
 mod command_handler;
 mod handlers;
+mod daemon;
 
 use clap::Parser;
-use dotenvy;
 use std::process;
 use cli::Cli;
 use crate::command_handler::CommandHandler;
@@ -22,6 +22,6 @@ fn main() {
     let mut handler = CommandHandler::new();
     if let Err(error) = handler.handle_command(cli.command) {
         eprintln!("{}", error);
-        process::exit(1);
+        process::exit(error.exit_code());
     }
 }
@@ -1,4 +1,17 @@
-use argos_core::process::model::{ProcessDelta, ProcessRow};
+use argos_core::commands::compare::ab::AbReport;
+use argos_core::commands::compare::gate::GateReport;
+use argos_core::commands::compare::series::SnapshotSeries;
+use argos_core::commands::bench::BenchReport;
+use argos_core::commands::run::{RunProfile, RunSummary};
+use argos_core::commands::family::FamilyTree;
+use argos_core::commands::live::LiveTick;
+use argos_core::commands::top::TopFrame;
+use argos_core::commands::zombies::ZombieReport;
+use argos_core::commands::leak::LeakReport;
+use argos_core::commands::sampling::SampleRun;
+use argos_core::commands::watchdog::backtest::BacktestReport;
+use argos_core::models::Alert;
+use argos_core::process::model::{ComparisonSummary, ProcessDelta, ProcessRow};
 use argos_export::{self};
 use crate::error::{CliResult, CliError};
 
@@ -20,18 +33,88 @@ impl OutputFormatter {
             .map_err(|e| CliError::format_error(format!("Error al exportar proceso: {}", e)))
     }
 
-    pub fn format_samples(&self, samples: &[ProcessRow], format: &str) -> CliResult<String> {
-        argos_export::format_samples_list(samples, format)
+    pub fn format_live_tick(&self, tick: &LiveTick, format: &str, header: bool) -> CliResult<String> {
+        argos_export::format_live_tick(tick, format, header)
+            .map_err(|e| CliError::format_error(format!("Error al exportar muestra: {}", e)))
+    }
+
+    pub fn format_top_frame(&self, frame: &TopFrame, format: &str, header: bool) -> CliResult<String> {
+        argos_export::format_top_frame(frame, format, header)
+            .map_err(|e| CliError::format_error(format!("Error al exportar procesos: {}", e)))
+    }
+
+    pub fn format_zombies(&self, report: &ZombieReport, format: &str) -> CliResult<String> {
+        argos_export::format_zombie_report(report, format)
+            .map_err(|e| CliError::format_error(format!("Error al exportar zombies: {}", e)))
+    }
+
+    pub fn format_leak(&self, report: &LeakReport, format: &str) -> CliResult<String> {
+        argos_export::format_leak_report(report, format)
+            .map_err(|e| CliError::format_error(format!("Error al exportar análisis de fugas: {}", e)))
+    }
+
+    pub fn format_samples(&self, run: &SampleRun, format: &str) -> CliResult<String> {
+        argos_export::format_sample_run(run, format)
             .map_err(|e| CliError::format_error(format!("Error al exportar muestras: {}", e)))
     }
     
-    pub fn format_comparison(&self, comparison: &[ProcessDelta], format: &str) -> CliResult<String> {
-        argos_export::format_comparison(comparison, format)
+    pub fn format_run_summary(&self, summary: &RunSummary, format: &str) -> CliResult<String> {
+        argos_export::format_run_summary(summary, format)
+            .map_err(|e| CliError::format_error(format!("Error al exportar resumen: {}", e)))
+    }
+
+    pub fn format_run_profile(&self, profile: &RunProfile, format: &str) -> CliResult<String> {
+        argos_export::format_run_profile(profile, format)
+            .map_err(|e| CliError::format_error(format!("Error al exportar perfil: {}", e)))
+    }
+
+    pub fn format_bench(&self, report: &BenchReport, format: &str) -> CliResult<String> {
+        argos_export::format_bench_report(report, format)
+            .map_err(|e| CliError::format_error(format!("Error al exportar benchmark: {}", e)))
+    }
+
+    pub fn format_comparison(
+        &self,
+        comparison: &[ProcessDelta],
+        summary: &ComparisonSummary,
+        format: &str,
+    ) -> CliResult<String> {
+        argos_export::format_comparison(comparison, summary, format)
             .map_err(|e| CliError::format_error(format!("Error al exportar comparación: {}", e)))
     }
 
-    pub fn format_process_tree(&self, root: u32, rows: &[ProcessRow], format: &str) -> CliResult<String> {
-        argos_export::format_process_tree(root, rows, format)
+    pub fn format_alerts(&self, alerts: &[Alert], format: &str) -> CliResult<String> {
+        argos_export::format_alert_list(alerts, format)
+            .map_err(|e| CliError::format_error(format!("Error al exportar alertas: {}", e)))
+    }
+
+    pub fn format_alert(&self, alert: &Alert, format: &str) -> CliResult<String> {
+        argos_export::format_alert_detail(alert, format)
+            .map_err(|e| CliError::format_error(format!("Error al exportar alerta: {}", e)))
+    }
+
+    pub fn format_backtest(&self, report: &BacktestReport, format: &str) -> CliResult<String> {
+        argos_export::format_backtest_report(report, format)
+            .map_err(|e| CliError::format_error(format!("Error al exportar backtest: {}", e)))
+    }
+
+    pub fn format_series(&self, series: &SnapshotSeries, format: &str) -> CliResult<String> {
+        argos_export::format_series(series, format)
+            .map_err(|e| CliError::format_error(format!("Error al exportar serie de snapshots: {}", e)))
+    }
+
+    pub fn format_gate(&self, report: &GateReport, format: &str) -> CliResult<String> {
+        argos_export::format_gate_report(report, format)
+            .map_err(|e| CliError::format_error(format!("Error al exportar umbrales: {}", e)))
+    }
+
+    pub fn format_ab(&self, report: &AbReport, format: &str) -> CliResult<String> {
+        argos_export::format_ab_report(report, format)
+            .map_err(|e| CliError::format_error(format!("Error al exportar comparación A/B: {}", e)))
+    }
+
+    pub fn format_process_tree(&self, tree: &FamilyTree, format: &str) -> CliResult<String> {
+        argos_export::format_process_tree(tree, format)
             .map_err(|e| CliError::format_error(format!("Error al exportar árbol de procesos: {}", e)))
     }
 }
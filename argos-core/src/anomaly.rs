@@ -0,0 +1,382 @@
+//! Detección en línea de anomalías sobre las métricas de cada proceso.
+//!
+//! Cada par (proceso, métrica) mantiene su propia línea base: la mediana y la
+//! MAD de una ventana móvil dan el nivel y la dispersión habituales sin que
+//! los picos los arrastren, y una media móvil exponencial (EWMA) de los valores
+//! recientes detecta cambios de nivel sostenidos. Así los umbrales se adaptan
+//! a cada servicio en lugar de ser fijos.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::types::ProcessMetrics;
+use crate::stats::{median, median_absolute_deviation};
+
+/// Factor que convierte la MAD en una estimación de la desviación típica.
+const MAD_TO_SIGMA: f64 = 1.4826;
+/// Dispersión mínima relativa al nivel: en una serie casi plana, variaciones
+/// del 2 % no cuentan como anomalía.
+const MIN_RELATIVE_SCALE: f64 = 0.02;
+
+/// Métricas sobre las que se buscan anomalías.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyMetric {
+    Cpu,
+    Memory,
+    DiskRead,
+    DiskWrite,
+    OpenFds,
+    Threads,
+}
+
+impl AnomalyMetric {
+    pub const ALL: [AnomalyMetric; 6] = [
+        AnomalyMetric::Cpu,
+        AnomalyMetric::Memory,
+        AnomalyMetric::DiskRead,
+        AnomalyMetric::DiskWrite,
+        AnomalyMetric::OpenFds,
+        AnomalyMetric::Threads,
+    ];
+
+    /// Valor de la métrica, si se conoce en esta observación.
+    pub fn value(self, m: &ProcessMetrics) -> Option<f64> {
+        match self {
+            AnomalyMetric::Cpu => Some(m.cpu_usage as f64),
+            AnomalyMetric::Memory => Some(m.memory_bytes as f64),
+            AnomalyMetric::DiskRead => m.disk_read_per_sec,
+            AnomalyMetric::DiskWrite => m.disk_write_per_sec,
+            AnomalyMetric::OpenFds => m.open_fds.map(|v| v as f64),
+            AnomalyMetric::Threads => m.threads.map(|v| v as f64),
+        }
+    }
+
+    /// Dispersión mínima en las unidades de la métrica, para que un proceso
+    /// ocioso (CPU siempre a 0) no dispare con cualquier actividad.
+    fn min_scale(self) -> f64 {
+        match self {
+            AnomalyMetric::Cpu => 2.0,                                       // puntos porcentuales
+            AnomalyMetric::Memory => 1_048_576.0,                            // 1 MB
+            AnomalyMetric::DiskRead | AnomalyMetric::DiskWrite => 262_144.0, // 256 KB/s
+            AnomalyMetric::OpenFds | AnomalyMetric::Threads => 1.0,
+        }
+    }
+}
+
+impl FromStr for AnomalyMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "cpu" => Ok(AnomalyMetric::Cpu),
+            "mem" | "memory" => Ok(AnomalyMetric::Memory),
+            "read" | "disk_read" => Ok(AnomalyMetric::DiskRead),
+            "write" | "disk_write" => Ok(AnomalyMetric::DiskWrite),
+            "fds" | "open_fds" => Ok(AnomalyMetric::OpenFds),
+            "threads" => Ok(AnomalyMetric::Threads),
+            other => Err(format!(
+                "Métrica de anomalía desconocida: {} (use cpu, mem, read, write, fds o threads)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for AnomalyMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnomalyMetric::Cpu => write!(f, "cpu"),
+            AnomalyMetric::Memory => write!(f, "mem"),
+            AnomalyMetric::DiskRead => write!(f, "read"),
+            AnomalyMetric::DiskWrite => write!(f, "write"),
+            AnomalyMetric::OpenFds => write!(f, "fds"),
+            AnomalyMetric::Threads => write!(f, "threads"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    Spike,      // un valor suelto muy lejos de lo habitual
+    LevelShift, // el nivel se mantiene desplazado varias observaciones seguidas
+}
+
+impl fmt::Display for AnomalyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnomalyKind::Spike => write!(f, "pico"),
+            AnomalyKind::LevelShift => write!(f, "cambio de nivel"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+impl Severity {
+    /// Gravedad según cuánto supera la puntuación al umbral que la marcó.
+    fn from_score(score: f64, threshold: f64) -> Self {
+        let ratio = score / threshold;
+        if ratio >= 3.0 {
+            Severity::High
+        } else if ratio >= 1.5 {
+            Severity::Medium
+        } else {
+            Severity::Low
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Low => write!(f, "baja"),
+            Severity::Medium => write!(f, "media"),
+            Severity::High => write!(f, "alta"),
+        }
+    }
+}
+
+/// Anomalía en curso en una métrica de un proceso.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Anomaly {
+    pub metric: AnomalyMetric,
+    pub kind: AnomalyKind,
+    pub value: f64,    // último valor observado
+    pub expected: f64, // mediana de la línea base
+    pub score: f64,    // desviaciones típicas robustas respecto a la línea base
+    pub severity: Severity,
+    pub onset: bool, // primera observación de la anomalía
+}
+
+/// Comienzo de una anomalía en un proceso concreto.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnomalyEvent {
+    pub pid: u32,
+    pub name: String,
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub anomaly: Anomaly,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnomalyConfig {
+    /// Observaciones que forman la línea base
+    pub window: usize,
+    /// Observaciones necesarias antes de marcar nada
+    pub warmup: usize,
+    /// Peso de la observación nueva en la EWMA de cambios de nivel
+    pub alpha: f64,
+    /// Puntuación a partir de la que un valor suelto es un pico
+    pub spike_threshold: f64,
+    /// Puntuación de la EWMA a partir de la que hay un cambio de nivel...
+    pub shift_threshold: f64,
+    /// ...si se mantiene durante estas observaciones seguidas
+    pub shift_ticks: usize,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self { window: 60, warmup: 10, alpha: 0.5, spike_threshold: 4.0, shift_threshold: 3.0, shift_ticks: 3 }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Baseline {
+    window: VecDeque<f64>,
+    ewma: Option<f64>,  // con los valores recortados: decide si hay cambio de nivel
+    level: Option<f64>, // con los valores tal cual: nivel reciente real
+    shift_run: usize,   // observaciones seguidas con la EWMA fuera de banda
+    shifted: bool,
+    spiking: bool,
+}
+
+impl Baseline {
+    fn observe(&mut self, metric: AnomalyMetric, value: f64, config: &AnomalyConfig) -> Option<Anomaly> {
+        let smooth = |prev: Option<f64>, value: f64| prev.map_or(value, |prev| prev + config.alpha * (value - prev));
+        let previous_level = self.level.unwrap_or(value);
+        self.level = Some(smooth(self.level, value));
+
+        if self.window.len() < config.warmup.max(2) {
+            self.ewma = self.level;
+            self.push(value, config);
+            return None;
+        }
+
+        let history: Vec<f64> = self.window.iter().copied().collect();
+        let center = median(&history);
+        let scale = (MAD_TO_SIGMA * median_absolute_deviation(&history))
+            .max(center.abs() * MIN_RELATIVE_SCALE)
+            .max(metric.min_scale());
+        self.push(value, config);
+
+        // La EWMA se alimenta con el valor recortado para que un pico
+        // aislado, por grande que sea, no parezca un cambio de nivel
+        let limit = config.spike_threshold * scale;
+        let ewma = smooth(self.ewma, value.clamp(center - limit, center + limit));
+        self.ewma = Some(ewma);
+        let score = |from: f64| (value - from).abs() / scale;
+
+        let anomaly = |kind, score: f64, threshold, onset| Anomaly {
+            metric,
+            kind,
+            value,
+            expected: center,
+            score,
+            severity: Severity::from_score(score, threshold),
+            onset,
+        };
+
+        // Un cambio de nivel dura hasta que la línea base lo absorbe (la
+        // mediana de la ventana se acerca al nivel nuevo)
+        if (ewma - center).abs() / scale >= config.shift_threshold {
+            self.shift_run += 1;
+        } else {
+            self.shift_run = 0;
+            self.shifted = false;
+        }
+        if self.shift_run >= config.shift_ticks {
+            let onset = !self.shifted;
+            self.shifted = true;
+            self.spiking = false;
+            let level = self.level.unwrap_or(value);
+            let shift_score = (level - center).abs() / scale;
+            return Some(anomaly(AnomalyKind::LevelShift, shift_score, config.shift_threshold, onset));
+        }
+
+        // Pico: lejos de la línea base y también del nivel reciente (si el
+        // proceso ya estaba en ese nivel no es un valor suelto)
+        if score(center) >= config.spike_threshold && score(previous_level) >= config.spike_threshold {
+            let onset = !self.spiking;
+            self.spiking = true;
+            return Some(anomaly(AnomalyKind::Spike, score(center), config.spike_threshold, onset));
+        }
+        self.spiking = false;
+        None
+    }
+
+    fn push(&mut self, value: f64, config: &AnomalyConfig) {
+        self.window.push_back(value);
+        while self.window.len() > config.window.max(1) {
+            self.window.pop_front();
+        }
+    }
+}
+
+/// Líneas base por proceso y métrica. Cada observación se compara con la
+/// línea base antes de incorporarse a ella.
+#[derive(Debug, Default)]
+pub struct AnomalyDetector {
+    config: AnomalyConfig,
+    baselines: HashMap<(u32, AnomalyMetric), Baseline>,
+}
+
+impl AnomalyDetector {
+    pub fn new(config: AnomalyConfig) -> Self {
+        Self { config, baselines: HashMap::new() }
+    }
+
+    /// Registra las métricas del proceso y devuelve las anomalías en curso.
+    pub fn observe(&mut self, pid: u32, metrics: &ProcessMetrics) -> Vec<Anomaly> {
+        AnomalyMetric::ALL
+            .iter()
+            .filter_map(|&metric| {
+                let value = metric.value(metrics)?;
+                self.baselines.entry((pid, metric)).or_default().observe(metric, value, &self.config)
+            })
+            .collect()
+    }
+
+    /// Olvida las líneas base de los procesos que ya no existen.
+    pub fn retain(&mut self, alive: &HashSet<u32>) {
+        self.baselines.retain(|(pid, _), _| alive.contains(pid));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pasa la serie por una línea base nueva con la configuración por defecto.
+    fn run(metric: AnomalyMetric, series: &[f64]) -> Vec<Option<Anomaly>> {
+        let config = AnomalyConfig::default();
+        let mut baseline = Baseline::default();
+        series.iter().map(|&v| baseline.observe(metric, v, &config)).collect()
+    }
+
+    /// Serie estable en torno a `level` con ruido determinista de ±3.
+    fn noisy(level: f64, len: usize) -> Vec<f64> {
+        (0..len).map(|i| level + ((i * 37) % 7) as f64 - 3.0).collect()
+    }
+
+    fn kinds(result: &[Option<Anomaly>]) -> Vec<Option<AnomalyKind>> {
+        result.iter().map(|a| a.as_ref().map(|a| a.kind)).collect()
+    }
+
+    #[test]
+    fn nothing_is_flagged_during_warmup() {
+        let series: Vec<f64> = (0..10).map(|i| if i % 2 == 0 { 0.0 } else { 90.0 }).collect();
+        assert!(run(AnomalyMetric::Cpu, &series).iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn noisy_but_stable_series_is_quiet() {
+        assert!(run(AnomalyMetric::Cpu, &noisy(20.0, 200)).iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn idle_process_ignores_small_activity() {
+        let mut series = vec![0.0; 30];
+        series.extend([5.0, 0.0, 6.0, 0.0]);
+        assert!(run(AnomalyMetric::Cpu, &series).iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn isolated_value_is_a_spike() {
+        let mut series = noisy(20.0, 30);
+        series.push(90.0);
+        series.extend(noisy(20.0, 30));
+        let result = run(AnomalyMetric::Cpu, &series);
+
+        let spike = result[30].as_ref().expect("el pico debe marcarse");
+        assert_eq!(spike.kind, AnomalyKind::Spike);
+        assert!(spike.onset);
+        assert_eq!(spike.value, 90.0);
+        assert_eq!(spike.severity, Severity::High);
+        // Ni antes ni después hay nada: el pico no desplaza la línea base
+        assert_eq!(result.iter().filter(|a| a.is_some()).count(), 1);
+    }
+
+    #[test]
+    fn sustained_change_is_a_level_shift_until_absorbed() {
+        let mut series = noisy(20.0, 30);
+        series.extend(vec![60.0; 80]);
+        let result = run(AnomalyMetric::Cpu, &series);
+        let after = &kinds(&result)[30..];
+
+        // El primer valor nuevo parece un pico; en pocas observaciones pasa a cambio de nivel
+        assert_eq!(after[0], Some(AnomalyKind::Spike));
+        let shift = after.iter().position(|k| *k == Some(AnomalyKind::LevelShift)).expect("cambio de nivel");
+        assert!(shift <= AnomalyConfig::default().shift_ticks + 1, "cambio de nivel tras {shift} observaciones");
+        let onsets = result[30..]
+            .iter()
+            .flatten()
+            .filter(|a| a.kind == AnomalyKind::LevelShift && a.onset)
+            .count();
+        assert_eq!(onsets, 1);
+
+        // Cuando la mediana de la ventana alcanza el nivel nuevo deja de ser anomalía
+        assert!(after[after.len() - 20..].iter().all(Option::is_none));
+    }
+}
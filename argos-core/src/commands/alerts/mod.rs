@@ -0,0 +1,37 @@
+use chrono::Utc;
+
+use crate::commands::watchdog::engine::ActionRecord;
+use crate::db::alert::{self, AlertFilter};
+use crate::db::manager::connect;
+use crate::errors::{CoreError, CoreResult};
+use crate::models::Alert;
+
+/// Alertas registradas por el watchdog, de la más reciente a la más antigua.
+pub fn list_alerts(database_url: Option<&str>, filter: &AlertFilter) -> CoreResult<Vec<Alert>> {
+    let mut conn = connect(database_url)?;
+    Ok(alert::list_alerts(&mut conn, filter)?)
+}
+
+pub fn get_alert(database_url: Option<&str>, id: i32) -> CoreResult<Alert> {
+    let mut conn = connect(database_url)?;
+    alert::get_alert_by_id(&mut conn, id).map_err(|e| match e {
+        diesel::result::Error::NotFound => CoreError::Other(format!("No existe la alerta {}", id)),
+        other => other.into(),
+    })
+}
+
+/// Marca una alerta como reconocida y la devuelve actualizada.
+pub fn ack_alert(database_url: Option<&str>, id: i32, note: Option<&str>) -> CoreResult<Alert> {
+    let mut conn = connect(database_url)?;
+    if alert::ack_alert(&mut conn, id, Utc::now().naive_utc(), note)? == 0 {
+        return Err(CoreError::Other(format!("No existe la alerta {}", id)));
+    }
+    Ok(alert::get_alert_by_id(&mut conn, id)?)
+}
+
+impl Alert {
+    /// Acciones ejecutadas durante la alerta (columna `actions`).
+    pub fn action_records(&self) -> Vec<ActionRecord> {
+        serde_json::from_str(&self.actions).unwrap_or_default()
+    }
+}
@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    commands::run::{profile_command, RunSummary},
+    errors::{CoreError, CoreResult},
+    stats::{mann_whitney_u, mean, median, std_dev, tukey_outliers},
+};
+
+/// Cómo repetir el comando.
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    pub runs: usize,
+    pub warmup: usize,
+    pub interval_ms: u64,
+    pub show_output: bool,
+}
+
+/// Medidas de una ejecución.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunMeasurement {
+    pub run: usize,
+    pub wall_secs: f64,
+    pub cpu_secs: f64, // usuario + sistema
+    pub peak_rss_mb: f64,
+    pub exit_code: Option<i32>,
+}
+
+impl RunMeasurement {
+    fn from_summary(run: usize, summary: &RunSummary) -> Self {
+        let cpu_secs = match (summary.user_cpu_secs, summary.system_cpu_secs) {
+            (Some(user), Some(system)) => user + system,
+            // Sin rusage se estima con la CPU media muestreada
+            _ => summary.avg_cpu / 100.0 * summary.wall_secs,
+        };
+        Self {
+            run,
+            wall_secs: summary.wall_secs,
+            cpu_secs,
+            peak_rss_mb: summary.peak_rss_mb,
+            exit_code: summary.exit_code,
+        }
+    }
+}
+
+/// Estadísticos de una métrica sobre todas las ejecuciones medidas.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricStats {
+    pub metric: String,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub outliers: Vec<usize>, // números de ejecución (desde 1)
+}
+
+impl MetricStats {
+    fn new(metric: &str, runs: &[RunMeasurement], value: fn(&RunMeasurement) -> f64) -> Self {
+        let values: Vec<f64> = runs.iter().map(value).collect();
+        Self {
+            metric: metric.to_string(),
+            mean: mean(&values),
+            std_dev: std_dev(&values),
+            median: median(&values),
+            min: values.iter().cloned().fold(f64::INFINITY, f64::min),
+            max: values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            outliers: tukey_outliers(&values).into_iter().map(|i| runs[i].run).collect(),
+        }
+    }
+}
+
+type Metric = (&'static str, fn(&RunMeasurement) -> f64);
+
+const METRICS: [Metric; 3] = [
+    ("wall_secs", |r| r.wall_secs),
+    ("cpu_secs", |r| r.cpu_secs),
+    ("peak_rss_mb", |r| r.peak_rss_mb),
+];
+
+/// Resultado de repetir un comando.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub command: String,
+    pub warmup: usize,
+    pub runs: Vec<RunMeasurement>,
+    pub failed_runs: usize, // con código de salida distinto de 0
+    pub metrics: Vec<MetricStats>,
+}
+
+/// Diferencia de una métrica entre el segundo comando (B) y el primero (A).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchDelta {
+    pub metric: String,
+    pub a_mean: f64,
+    pub b_mean: f64,
+    pub ratio: Option<f64>, // B / A; sin definir si A es 0
+    pub p_value: f64,       // Mann-Whitney
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub results: Vec<BenchResult>,
+    pub comparison: Option<Vec<BenchDelta>>,
+}
+
+/// Ejecuta `command` `warmup` veces sin medir y después `runs` veces,
+/// perfilando cada ejecución como `argos run`. `on_run` recibe el número de
+/// ejecución (desde 1), si era de calentamiento y su resumen.
+pub fn bench_command(
+    command: &[String],
+    options: &BenchOptions,
+    mut on_run: impl FnMut(usize, bool, &RunSummary),
+) -> CoreResult<BenchResult> {
+    if options.runs == 0 {
+        return Err(CoreError::ValidationError("El número de ejecuciones debe ser mayor que 0".into()));
+    }
+
+    for i in 1..=options.warmup {
+        let profile = profile_command(command, options.interval_ms, !options.show_output)?;
+        on_run(i, true, &profile.summary);
+    }
+
+    let mut runs = Vec::with_capacity(options.runs);
+    for i in 1..=options.runs {
+        let profile = profile_command(command, options.interval_ms, !options.show_output)?;
+        on_run(i, false, &profile.summary);
+        runs.push(RunMeasurement::from_summary(i, &profile.summary));
+    }
+
+    Ok(BenchResult {
+        command: command.join(" "),
+        warmup: options.warmup,
+        failed_runs: runs.iter().filter(|r| r.exit_code != Some(0)).count(),
+        metrics: METRICS.iter().map(|(name, value)| MetricStats::new(name, &runs, *value)).collect(),
+        runs,
+    })
+}
+
+/// Compara las ejecuciones de dos comandos métrica a métrica.
+pub fn compare_results(a: &BenchResult, b: &BenchResult) -> Vec<BenchDelta> {
+    METRICS
+        .iter()
+        .zip(a.metrics.iter().zip(&b.metrics))
+        .map(|((name, value), (stats_a, stats_b))| {
+            let values_a: Vec<f64> = a.runs.iter().map(value).collect();
+            let values_b: Vec<f64> = b.runs.iter().map(value).collect();
+            BenchDelta {
+                metric: name.to_string(),
+                a_mean: stats_a.mean,
+                b_mean: stats_b.mean,
+                ratio: (stats_a.mean != 0.0).then(|| stats_b.mean / stats_a.mean),
+                p_value: mann_whitney_u(&values_a, &values_b).p_value,
+            }
+        })
+        .collect()
+}
+
+/// Parte una línea de comando en argumentos, respetando comillas simples y
+/// dobles (sin expansiones de shell).
+pub fn split_command(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut in_arg = false;
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+
+    const HELPER_MB: usize = 64;
+
+    /// Comando de prueba con varios hilos: el propio binario de tests vuelve a
+    /// ejecutarse con solo esta prueba. Reserva `HELPER_MB` y los comparte con 8 hilos.
+    #[test]
+    #[ignore = "la lanza multithreaded_command_is_one_process"]
+    fn thread_helper() {
+        let buffer = vec![1u8; HELPER_MB << 20];
+        let handles: Vec<_> = (0..8).map(|_| thread::spawn(|| thread::sleep(Duration::from_millis(600)))).collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(buffer.iter().step_by(4096).all(|b| *b == 1));
+    }
+
+    #[test]
+    fn multithreaded_command_is_one_process() {
+        let exe = std::env::current_exe().unwrap().to_string_lossy().into_owned();
+        let command: Vec<String> = [exe.as_str(), "--exact", "commands::bench::tests::thread_helper", "--ignored"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let options = BenchOptions { runs: 1, warmup: 0, interval_ms: 20, show_output: false };
+
+        let mut children = Vec::new();
+        let result = bench_command(&command, &options, |_, _, summary| children.push(summary.children)).unwrap();
+
+        assert_eq!(children, vec![0]);
+        assert_eq!(result.failed_runs, 0);
+        // Al menos la memoria reservada, que los hilos comparten
+        let peak = result.runs[0].peak_rss_mb;
+        assert!(peak >= HELPER_MB as f64, "pico de RSS {peak} MB");
+    }
+}
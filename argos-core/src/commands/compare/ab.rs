@@ -0,0 +1,231 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::commands::sampling::SampleRun;
+use crate::db::manager::connect;
+use crate::db::sample::get_samples_by_log;
+use crate::errors::{CoreError, CoreResult};
+use crate::process::model::ProcessRow;
+use crate::stats::{bootstrap_interval, mann_whitney_u, mean, median, percentile, std_dev, welch_interval};
+
+/// Por debajo de estas muestras por grupo la prueba no tiene potencia y el
+/// veredicto es siempre inconcluso.
+pub const MIN_SAMPLES: usize = 5;
+const BOOTSTRAP_RESAMPLES: usize = 2000;
+
+/// Muestras de CPU (%) y memoria (MB) de una sesión o de un archivo exportado.
+#[derive(Debug, Clone)]
+pub struct SampleSet {
+    pub label: String,
+    pub cpu: Vec<f64>,
+    pub memory_mb: Vec<f64>,
+}
+
+impl SampleSet {
+    fn from_rows(label: String, rows: &[ProcessRow]) -> Self {
+        Self {
+            label,
+            cpu: rows.iter().map(|r| r.cpu_usage).collect(),
+            memory_mb: rows.iter().map(|r| r.memory_mb).collect(),
+        }
+    }
+}
+
+/// Muestras de una sesión guardada en la base de datos (columna `memory` en bytes).
+pub fn load_session(database_url: Option<&str>, id: &str) -> CoreResult<SampleSet> {
+    let mut conn = connect(database_url)?;
+    let samples = get_samples_by_log(&mut conn, id)?;
+    if samples.is_empty() {
+        return Err(CoreError::ComparisonError(format!("La sesión '{}' no existe o no tiene muestras", id)));
+    }
+    Ok(SampleSet {
+        label: id.to_string(),
+        cpu: samples.iter().map(|s| s.cpu_usage as f64).collect(),
+        memory_mb: samples.iter().map(|s| s.memory as f64 / 1_048_576.0).collect(),
+    })
+}
+
+/// Muestras exportadas con `argos sample` en JSON o CSV (según la extensión).
+pub fn load_sample_file(path: &Path) -> CoreResult<SampleSet> {
+    let data = std::fs::read_to_string(path).map_err(CoreError::Io)?;
+    let rows: Vec<ProcessRow> = if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("csv")) {
+        csv::Reader::from_reader(data.as_bytes())
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .map_err(|e| CoreError::ComparisonError(format!("CSV inválido en {}: {}", path.display(), e)))?
+    } else {
+        // `sample -f json` escribe `{samples, exited}`; se aceptan también listas sueltas
+        match serde_json::from_str::<SampleRun>(&data) {
+            Ok(run) => run.samples.into_iter().map(|s| s.process).collect(),
+            Err(_) => serde_json::from_str(&data).map_err(CoreError::Parse)?,
+        }
+    };
+    if rows.is_empty() {
+        return Err(CoreError::ComparisonError(format!("{} no contiene muestras", path.display())));
+    }
+    // Mezclar procesos distintos en una sola distribución no compara nada
+    let pids: BTreeSet<u32> = rows.iter().map(|r| r.pid).collect();
+    if pids.len() > 1 {
+        let list: Vec<String> = pids.iter().map(|p| p.to_string()).collect();
+        return Err(CoreError::ComparisonError(format!(
+            "{} contiene muestras de varios procesos (PIDs {}); exporte las de un solo PID con `argos sample`",
+            path.display(),
+            list.join(", ")
+        )));
+    }
+    Ok(SampleSet::from_rows(path.display().to_string(), &rows))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Improved,
+    Regressed,
+    Inconclusive,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Verdict::Improved => "mejora",
+            Verdict::Regressed => "empeora",
+            Verdict::Inconclusive => "inconcluso",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// Estadísticos de una métrica en un grupo.
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupStats {
+    pub samples: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub std_dev: f64,
+}
+
+impl GroupStats {
+    fn from_values(values: &[f64]) -> Self {
+        Self {
+            samples: values.len(),
+            mean: mean(values),
+            median: median(values),
+            p95: percentile(values, 95.0),
+            std_dev: std_dev(values),
+        }
+    }
+}
+
+/// Diferencia `B − A` de un estadístico con su intervalo de confianza.
+#[derive(Debug, Clone, Serialize)]
+pub struct Difference {
+    pub value: f64,
+    pub percent: Option<f64>, // respecto a A; sin definir si A es 0
+    pub ci_low: f64,
+    pub ci_high: f64,
+}
+
+impl Difference {
+    fn new(a: f64, b: f64, (ci_low, ci_high): (f64, f64)) -> Self {
+        let value = b - a;
+        Self {
+            value,
+            percent: (a != 0.0).then(|| value / a.abs() * 100.0),
+            ci_low,
+            ci_high,
+        }
+    }
+}
+
+/// Comparación A/B de una métrica (menor es mejor).
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricComparison {
+    pub metric: String,
+    pub a: GroupStats,
+    pub b: GroupStats,
+    pub mean_diff: Difference,
+    pub median_diff: Difference,
+    pub p95_diff: Difference,
+    pub mann_whitney_u: f64,
+    pub p_value: f64,
+    pub prob_b_greater: f64,
+    pub verdict: Verdict,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AbReport {
+    pub a: String,
+    pub b: String,
+    pub confidence: f64,
+    pub metrics: Vec<MetricComparison>,
+    pub verdict: Verdict,
+}
+
+fn compare_metric(metric: &str, a: &[f64], b: &[f64], confidence: f64) -> MetricComparison {
+    let (stats_a, stats_b) = (GroupStats::from_values(a), GroupStats::from_values(b));
+    let test = mann_whitney_u(a, b);
+    let alpha = 1.0 - confidence;
+
+    // La prueba decide si hay diferencia; la probabilidad de superioridad, el sentido
+    let verdict = if a.len() < MIN_SAMPLES || b.len() < MIN_SAMPLES || test.p_value >= alpha {
+        Verdict::Inconclusive
+    } else if test.prob_b_greater < 0.5 {
+        Verdict::Improved
+    } else {
+        Verdict::Regressed
+    };
+
+    MetricComparison {
+        metric: metric.to_string(),
+        mean_diff: Difference::new(stats_a.mean, stats_b.mean, welch_interval(a, b, confidence)),
+        median_diff: Difference::new(
+            stats_a.median,
+            stats_b.median,
+            bootstrap_interval(a, b, confidence, BOOTSTRAP_RESAMPLES, median),
+        ),
+        p95_diff: Difference::new(
+            stats_a.p95,
+            stats_b.p95,
+            bootstrap_interval(a, b, confidence, BOOTSTRAP_RESAMPLES, |v| percentile(v, 95.0)),
+        ),
+        a: stats_a,
+        b: stats_b,
+        mann_whitney_u: test.u,
+        p_value: test.p_value,
+        prob_b_greater: test.prob_b_greater,
+        verdict,
+    }
+}
+
+/// Compara dos grupos de muestras (A = antes, B = después) en CPU y memoria.
+///
+/// Cada métrica se contrasta con la prueba U de Mann-Whitney, que no asume
+/// normalidad: B mejora si es significativamente menor que A y empeora si es
+/// mayor. El veredicto global es `Regressed` si alguna métrica empeora e
+/// `Improved` si alguna mejora y ninguna empeora.
+pub fn compare_sessions(a: &SampleSet, b: &SampleSet, confidence: f64) -> AbReport {
+    let metrics = vec![
+        compare_metric("cpu_usage", &a.cpu, &b.cpu, confidence),
+        compare_metric("memory_mb", &a.memory_mb, &b.memory_mb, confidence),
+    ];
+
+    let verdict = if metrics.iter().any(|m| m.verdict == Verdict::Regressed) {
+        Verdict::Regressed
+    } else if metrics.iter().any(|m| m.verdict == Verdict::Improved) {
+        Verdict::Improved
+    } else {
+        Verdict::Inconclusive
+    };
+
+    AbReport {
+        a: a.label.clone(),
+        b: b.label.clone(),
+        confidence,
+        metrics,
+        verdict,
+    }
+}
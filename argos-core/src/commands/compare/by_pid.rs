@@ -5,6 +5,8 @@ use crate::{
 };
 use std::time::Duration;
 
+use super::matching::{compare_rows, MatchBy, Tolerance};
+
 /// Obtiene un sample único de los procesos indicados
 pub fn sample_process(pids: &[u32]) -> Result<Vec<ProcessRow>, CoreError> {
     if pids.is_empty() {
@@ -23,52 +25,7 @@ pub fn sample_process(pids: &[u32]) -> Result<Vec<ProcessRow>, CoreError> {
 
 /// Compara dos samples consecutivos de procesos
 pub fn compare_samples(old: &[ProcessRow], new: &[ProcessRow]) -> Vec<ProcessDelta> {
-    let mut diffs = Vec::new();
-
-    for new_row in new {
-        if let Some(old_row) = old.iter().find(|p| p.pid == new_row.pid) {
-            diffs.push(ProcessDelta {
-                pid: new_row.pid,
-                name: new_row.name.clone(),
-                cpu_before: old_row.cpu_usage,
-                cpu_after: new_row.cpu_usage,
-                cpu_delta: (new_row.cpu_usage - old_row.cpu_usage),
-                mem_before: old_row.memory_mb,
-                mem_after: new_row.memory_mb,
-                mem_delta: (new_row.memory_mb - old_row.memory_mb),
-            });
-        } else {
-            // Proceso nuevo
-            diffs.push(ProcessDelta {
-                pid: new_row.pid,
-                name: new_row.name.clone(),
-                cpu_before: 0.0,
-                cpu_after: new_row.cpu_usage,
-                cpu_delta: new_row.cpu_usage,
-                mem_before: 0.0,
-                mem_after: new_row.memory_mb,
-                mem_delta: new_row.memory_mb,
-            });
-        }
-    }
-
-    // Procesos que desaparecieron
-    for old_row in old {
-        if !new.iter().any(|p| p.pid == old_row.pid) {
-            diffs.push(ProcessDelta {
-                pid: old_row.pid,
-                name: old_row.name.clone(),
-                cpu_before: old_row.cpu_usage,
-                cpu_after: 0.0,
-                cpu_delta: -(old_row.cpu_usage),
-                mem_before: old_row.memory_mb,
-                mem_after: 0.0,
-                mem_delta: -(old_row.memory_mb),
-            });
-        }
-    }
-
-    diffs
+    compare_rows(old, new, MatchBy::Pid, Tolerance::default())
 }
 
 /// Ejemplo de uso en un loop de monitorización
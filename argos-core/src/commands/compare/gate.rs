@@ -0,0 +1,208 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::process::model::ProcessDelta;
+
+/// Métrica de un [`ProcessDelta`] sobre la que se puede poner un umbral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GateMetric {
+    CpuBefore,
+    CpuAfter,
+    CpuDelta,
+    CpuDeltaPct,
+    MemBefore,
+    MemAfter,
+    MemDelta,
+    MemDeltaPct,
+    InstancesBefore,
+    InstancesAfter,
+    InstancesDelta,
+}
+
+const METRICS: [(&str, GateMetric); 11] = [
+    ("cpu_before", GateMetric::CpuBefore),
+    ("cpu_after", GateMetric::CpuAfter),
+    ("cpu_delta", GateMetric::CpuDelta),
+    ("cpu_delta_pct", GateMetric::CpuDeltaPct),
+    ("mem_before", GateMetric::MemBefore),
+    ("mem_after", GateMetric::MemAfter),
+    ("mem_delta", GateMetric::MemDelta),
+    ("mem_delta_pct", GateMetric::MemDeltaPct),
+    ("instances_before", GateMetric::InstancesBefore),
+    ("instances_after", GateMetric::InstancesAfter),
+    ("instances_delta", GateMetric::InstancesDelta),
+];
+
+/// Variación porcentual respecto al valor inicial; sin valor inicial no está definida.
+fn percent(before: f64, delta: f64) -> Option<f64> {
+    (before > 0.0).then(|| delta / before * 100.0)
+}
+
+impl GateMetric {
+    /// Valor de la métrica para el proceso. Los porcentajes de procesos nuevos
+    /// (sin valor inicial) devuelven `None` y no se evalúan.
+    pub fn value(&self, delta: &ProcessDelta) -> Option<f64> {
+        match self {
+            GateMetric::CpuBefore => Some(delta.cpu_before),
+            GateMetric::CpuAfter => Some(delta.cpu_after),
+            GateMetric::CpuDelta => Some(delta.cpu_delta),
+            GateMetric::CpuDeltaPct => percent(delta.cpu_before, delta.cpu_delta),
+            GateMetric::MemBefore => Some(delta.mem_before),
+            GateMetric::MemAfter => Some(delta.mem_after),
+            GateMetric::MemDelta => Some(delta.mem_delta),
+            GateMetric::MemDeltaPct => percent(delta.mem_before, delta.mem_delta),
+            GateMetric::InstancesBefore => Some(delta.instances_before as f64),
+            GateMetric::InstancesAfter => Some(delta.instances_after as f64),
+            GateMetric::InstancesDelta => Some(delta.instances_after as f64 - delta.instances_before as f64),
+        }
+    }
+}
+
+impl fmt::Display for GateMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = METRICS.iter().find(|(_, m)| m == self).map_or("?", |(name, _)| name);
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparator {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl Comparator {
+    fn holds(&self, value: f64, limit: f64) -> bool {
+        match self {
+            Comparator::Greater => value > limit,
+            Comparator::GreaterOrEqual => value >= limit,
+            Comparator::Less => value < limit,
+            Comparator::LessOrEqual => value <= limit,
+        }
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Comparator::Greater => ">",
+            Comparator::GreaterOrEqual => ">=",
+            Comparator::Less => "<",
+            Comparator::LessOrEqual => "<=",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+/// Umbral de `--fail-if`, p. ej. `mem_delta_pct > 10`: la comparación falla si
+/// algún proceso cumple la expresión.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Threshold {
+    pub metric: GateMetric,
+    pub comparator: Comparator,
+    pub limit: f64,
+}
+
+impl FromStr for Threshold {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Los operadores de dos caracteres van primero para no partir `>=` en `>`
+        let operators = [
+            (">=", Comparator::GreaterOrEqual),
+            ("<=", Comparator::LessOrEqual),
+            (">", Comparator::Greater),
+            ("<", Comparator::Less),
+        ];
+        let (metric, comparator, limit) = operators
+            .iter()
+            .find_map(|(op, cmp)| s.split_once(op).map(|(m, l)| (m.trim(), *cmp, l.trim())))
+            .ok_or_else(|| format!("Umbral inválido (use <métrica> <op> <valor>): {}", s))?;
+
+        let metric = METRICS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(metric))
+            .map(|(_, m)| *m)
+            .ok_or_else(|| {
+                let names: Vec<&str> = METRICS.iter().map(|(name, _)| *name).collect();
+                format!("Métrica desconocida: {} (use {})", metric, names.join(", "))
+            })?;
+        let limit = limit
+            .trim_end_matches('%')
+            .parse::<f64>()
+            .map_err(|_| format!("Valor inválido en el umbral '{}': {}", s, limit))?;
+
+        Ok(Threshold { metric, comparator, limit })
+    }
+}
+
+impl fmt::Display for Threshold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.metric, self.comparator, self.limit)
+    }
+}
+
+/// Proceso que cumple un umbral.
+#[derive(Debug, Clone, Serialize)]
+pub struct GateViolation {
+    pub threshold: String,
+    pub pid: u32,
+    pub name: String,
+    pub value: f64,
+}
+
+/// Resultado de un umbral: procesos evaluados y los que lo incumplen.
+#[derive(Debug, Clone, Serialize)]
+pub struct ThresholdResult {
+    pub threshold: String,
+    pub evaluated: usize,
+    pub violations: Vec<GateViolation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GateReport {
+    pub processes: usize,
+    pub passed: bool,
+    pub thresholds: Vec<ThresholdResult>,
+}
+
+impl GateReport {
+    pub fn violations(&self) -> impl Iterator<Item = &GateViolation> {
+        self.thresholds.iter().flat_map(|t| t.violations.iter())
+    }
+}
+
+/// Evalúa los umbrales sobre una comparación. Pasa si ningún proceso cumple
+/// ninguno de ellos.
+pub fn evaluate_gate(deltas: &[ProcessDelta], thresholds: &[Threshold]) -> GateReport {
+    let thresholds: Vec<ThresholdResult> = thresholds
+        .iter()
+        .map(|threshold| {
+            let mut evaluated = 0;
+            let mut violations = Vec::new();
+            for delta in deltas {
+                let Some(value) = threshold.metric.value(delta) else { continue };
+                evaluated += 1;
+                if threshold.comparator.holds(value, threshold.limit) {
+                    violations.push(GateViolation {
+                        threshold: threshold.to_string(),
+                        pid: delta.pid,
+                        name: delta.name.clone(),
+                        value,
+                    });
+                }
+            }
+            ThresholdResult { threshold: threshold.to_string(), evaluated, violations }
+        })
+        .collect();
+
+    GateReport {
+        processes: deltas.len(),
+        passed: thresholds.iter().all(|t| t.violations.is_empty()),
+        thresholds,
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::process::filter::thread_row_pids;
+use crate::process::model::{DeltaStatus, ProcessDelta, ProcessRow};
+
+/// Cómo se identifica un mismo proceso en dos snapshots. Con `Name`, `Cmd` o
+/// `Exe` los procesos que comparten clave se suman en un solo agregado.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchBy {
+    #[default]
+    Pid,
+    Name,
+    Cmd,
+    Exe,
+}
+
+impl MatchBy {
+    /// Clave del proceso. Sin línea de comandos o ejecutable (hilos del kernel,
+    /// procesos de otros usuarios) se usa el nombre entre corchetes.
+    pub fn key(&self, row: &ProcessRow) -> String {
+        let or_name = |value: &str| {
+            if value.is_empty() || value == "-" {
+                format!("[{}]", row.name)
+            } else {
+                value.to_string()
+            }
+        };
+        match self {
+            MatchBy::Pid => row.pid.to_string(),
+            MatchBy::Name => row.name.clone(),
+            MatchBy::Cmd => or_name(&row.cmd),
+            MatchBy::Exe => or_name(&row.exe),
+        }
+    }
+}
+
+impl FromStr for MatchBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pid" => Ok(MatchBy::Pid),
+            "name" => Ok(MatchBy::Name),
+            "cmd" => Ok(MatchBy::Cmd),
+            "exe" => Ok(MatchBy::Exe),
+            other => Err(format!("Criterio de emparejamiento desconocido: {} (use pid, name, cmd o exe)", other)),
+        }
+    }
+}
+
+impl fmt::Display for MatchBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MatchBy::Pid => "pid",
+            MatchBy::Name => "name",
+            MatchBy::Cmd => "cmd",
+            MatchBy::Exe => "exe",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Variación máxima para considerar que un proceso presente en ambas muestras
+/// no cambió: puntos de CPU y MB de memoria.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    pub cpu: f64,
+    pub memory_mb: f64,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self { cpu: 1.0, memory_mb: 1.0 }
+    }
+}
+
+impl Tolerance {
+    fn status(&self, delta: &ProcessDelta) -> DeltaStatus {
+        if delta.instances_before != delta.instances_after
+            || delta.cpu_delta.abs() > self.cpu
+            || delta.mem_delta.abs() > self.memory_mb
+        {
+            DeltaStatus::Changed
+        } else {
+            DeltaStatus::Unchanged
+        }
+    }
+}
+
+/// Procesos de un snapshot que comparten clave, sumados.
+#[derive(Debug, Clone)]
+pub struct ProcessGroup {
+    pub key: String,
+    pub pid: u32, // el menor PID del grupo
+    pub name: String,
+    pub cpu_usage: f64,
+    pub memory_mb: f64,
+    pub instances: u32,
+}
+
+/// Agrupa las filas por clave, conservando el orden de primera aparición. Los
+/// hilos que guardan los snapshots antiguos no cuentan como instancias.
+pub fn group_rows(rows: &[ProcessRow], match_by: MatchBy) -> Vec<ProcessGroup> {
+    let mut groups: Vec<ProcessGroup> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let threads = thread_row_pids(rows);
+
+    for row in rows.iter().filter(|r| !threads.contains(&r.pid)) {
+        let key = match_by.key(row);
+        match index.get(&key) {
+            Some(&i) => {
+                let group = &mut groups[i];
+                group.pid = group.pid.min(row.pid);
+                group.cpu_usage += row.cpu_usage;
+                group.memory_mb += row.memory_mb;
+                group.instances += 1;
+            }
+            None => {
+                index.insert(key.clone(), groups.len());
+                groups.push(ProcessGroup {
+                    name: if match_by == MatchBy::Pid { row.name.clone() } else { key.clone() },
+                    key,
+                    pid: row.pid,
+                    cpu_usage: row.cpu_usage,
+                    memory_mb: row.memory_mb,
+                    instances: 1,
+                });
+            }
+        }
+    }
+
+    groups
+}
+
+/// Compara dos snapshots emparejando los procesos según `match_by`. El resultado
+/// se ordena por estado: nuevos, terminados, cambiados y sin cambios (según
+/// `tolerance`); dentro de cada estado se conserva el orden del snapshot.
+pub fn compare_rows(old: &[ProcessRow], new: &[ProcessRow], match_by: MatchBy, tolerance: Tolerance) -> Vec<ProcessDelta> {
+    let old = group_rows(old, match_by);
+    let new = group_rows(new, match_by);
+    let mut deltas = Vec::new();
+
+    for after in &new {
+        let before = old.iter().find(|g| g.key == after.key);
+        let mut delta = ProcessDelta {
+            pid: after.pid,
+            name: after.name.clone(),
+            status: DeltaStatus::Added,
+            cpu_before: before.map_or(0.0, |b| b.cpu_usage),
+            cpu_after: after.cpu_usage,
+            cpu_delta: after.cpu_usage - before.map_or(0.0, |b| b.cpu_usage),
+            mem_before: before.map_or(0.0, |b| b.memory_mb),
+            mem_after: after.memory_mb,
+            mem_delta: after.memory_mb - before.map_or(0.0, |b| b.memory_mb),
+            instances_before: before.map_or(0, |b| b.instances),
+            instances_after: after.instances,
+        };
+        if before.is_some() {
+            delta.status = tolerance.status(&delta);
+        }
+        deltas.push(delta);
+    }
+
+    // procesos que desaparecieron
+    for before in old.iter().filter(|b| !new.iter().any(|a| a.key == b.key)) {
+        deltas.push(ProcessDelta {
+            pid: before.pid,
+            name: before.name.clone(),
+            status: DeltaStatus::Removed,
+            cpu_before: before.cpu_usage,
+            cpu_after: 0.0,
+            cpu_delta: -before.cpu_usage,
+            mem_before: before.memory_mb,
+            mem_after: 0.0,
+            mem_delta: -before.memory_mb,
+            instances_before: before.instances,
+            instances_after: 0,
+        });
+    }
+
+    deltas.sort_by_key(|d| d.status);
+    deltas
+}
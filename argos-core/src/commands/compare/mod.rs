@@ -1,2 +1,6 @@
+pub mod ab;
 pub mod by_file;
-pub mod by_pid;
\ No newline at end of file
+pub mod by_pid;
+pub mod gate;
+pub mod matching;
+pub mod series;
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::commands::compare::by_file::load_snapshot;
+use crate::commands::compare::matching::{group_rows, MatchBy};
+use crate::errors::CoreError;
+
+/// Valor de un proceso en un snapshot (formato largo: una fila por proceso y snapshot).
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesPoint {
+    pub pid: u32,
+    pub name: String,
+    pub snapshot: usize,
+    pub file: String,
+    pub instances: u32,
+    pub cpu_usage: f64,
+    pub memory_mb: f64,
+}
+
+/// Primer y último valor, pico y cambio neto de una métrica a lo largo de la serie.
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricSummary {
+    pub first: f64,
+    pub last: f64,
+    pub peak: f64,
+    pub net_change: f64,
+}
+
+impl MetricSummary {
+    fn from_values(values: &[f64]) -> Self {
+        let first = values.first().copied().unwrap_or(0.0);
+        let last = values.last().copied().unwrap_or(0.0);
+        Self {
+            first,
+            last,
+            peak: values.iter().copied().fold(f64::NEG_INFINITY, f64::max).max(0.0),
+            net_change: last - first,
+        }
+    }
+}
+
+/// Trayectoria de un proceso (o agregado). `cpu` y `memory` tienen un valor por
+/// snapshot (`None` donde no aparece) e `instances` los procesos agregados en cada uno.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessTrajectory {
+    pub pid: u32,
+    pub name: String,
+    pub present_in: usize,
+    pub instances: Vec<u32>,
+    pub cpu: Vec<Option<f64>>,
+    pub memory: Vec<Option<f64>>,
+    pub cpu_summary: MetricSummary,
+    pub memory_summary: MetricSummary,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotSeries {
+    pub snapshots: Vec<String>,
+    pub processes: Vec<ProcessTrajectory>,
+}
+
+impl SnapshotSeries {
+    /// Filas en formato largo (tidy), en orden de proceso y snapshot.
+    pub fn points(&self) -> Vec<SeriesPoint> {
+        self.processes
+            .iter()
+            .flat_map(|p| {
+                p.cpu.iter().zip(&p.memory).enumerate().filter_map(move |(i, (cpu, mem))| {
+                    Some(SeriesPoint {
+                        pid: p.pid,
+                        name: p.name.clone(),
+                        snapshot: i,
+                        file: self.snapshots[i].clone(),
+                        instances: p.instances[i],
+                        cpu_usage: (*cpu)?,
+                        memory_mb: (*mem)?,
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+/// Compara N snapshots como una serie temporal, en el orden en que se indican.
+/// Los procesos se emparejan según `match_by`.
+pub fn compare_series(files: &[PathBuf], match_by: MatchBy) -> Result<SnapshotSeries, CoreError> {
+    if files.len() < 2 {
+        return Err(CoreError::ComparisonError("Se necesitan al menos dos archivos para comparar".into()));
+    }
+
+    let mut by_key: HashMap<String, ProcessTrajectory> = HashMap::new();
+    for (i, file) in files.iter().enumerate() {
+        for row in group_rows(&load_snapshot(file)?, match_by) {
+            let trajectory = by_key.entry(row.key).or_insert_with(|| ProcessTrajectory {
+                pid: row.pid,
+                name: row.name.clone(),
+                present_in: 0,
+                instances: vec![0; files.len()],
+                cpu: vec![None; files.len()],
+                memory: vec![None; files.len()],
+                cpu_summary: MetricSummary::from_values(&[]),
+                memory_summary: MetricSummary::from_values(&[]),
+            });
+            trajectory.present_in += 1;
+            trajectory.instances[i] = row.instances;
+            trajectory.cpu[i] = Some(row.cpu_usage);
+            trajectory.memory[i] = Some(row.memory_mb);
+        }
+    }
+
+    let mut processes: Vec<ProcessTrajectory> = by_key
+        .into_values()
+        .map(|mut p| {
+            let cpu: Vec<f64> = p.cpu.iter().flatten().copied().collect();
+            let memory: Vec<f64> = p.memory.iter().flatten().copied().collect();
+            p.cpu_summary = MetricSummary::from_values(&cpu);
+            p.memory_summary = MetricSummary::from_values(&memory);
+            p
+        })
+        .collect();
+    if match_by == MatchBy::Pid {
+        processes.sort_by_key(|p| p.pid);
+    } else {
+        processes.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    Ok(SnapshotSeries {
+        snapshots: files
+            .iter()
+            .map(|f| f.file_stem().unwrap_or(f.as_os_str()).to_string_lossy().into_owned())
+            .collect(),
+        processes,
+    })
+}
@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
 use crate::{errors::{CoreError, CoreResult}, process::{model::ProcessRow, reader::ProcessReader}};
 
 pub fn get_family(pid: u32) -> CoreResult<Vec<ProcessRow>> {
@@ -11,3 +15,228 @@ pub fn get_family(pid: u32) -> CoreResult<Vec<ProcessRow>> {
         Err(CoreError::ProcessNotFound(pid))
     }
 }
+
+/// Qué parte del árbol de procesos mostrar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FamilyView {
+    /// El proceso y sus descendientes
+    Descendants(u32),
+    /// La cadena de padres desde la raíz hasta el proceso
+    Ancestors(u32),
+    /// Todo el sistema, como `pstree`
+    All,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FamilyOptions {
+    pub view: FamilyView,
+    /// Niveles a mostrar: bajo cada raíz, o hacia arriba con `Ancestors`
+    pub depth: Option<usize>,
+    /// Agrupa hermanos con el mismo nombre y la misma forma de subárbol
+    pub collapse: bool,
+}
+
+/// Recursos de un nodo más todos sus descendientes (también los que no se
+/// muestran por el límite de profundidad).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct SubtreeTotals {
+    pub cpu_usage: f64,
+    pub memory_mb: f64,
+    pub read_disk_usage: f64,  // MB
+    pub write_disk_usage: f64, // MB
+    pub descendants: usize,
+}
+
+impl SubtreeTotals {
+    fn of(row: &ProcessRow) -> Self {
+        Self {
+            cpu_usage: row.cpu_usage,
+            memory_mb: row.memory_mb,
+            read_disk_usage: row.read_disk_usage,
+            write_disk_usage: row.write_disk_usage,
+            descendants: 0,
+        }
+    }
+
+    fn add(&mut self, other: &SubtreeTotals) {
+        self.cpu_usage += other.cpu_usage;
+        self.memory_mb += other.memory_mb;
+        self.read_disk_usage += other.read_disk_usage;
+        self.write_disk_usage += other.write_disk_usage;
+        self.descendants += other.descendants;
+    }
+}
+
+/// Nodo del árbol. Con hermanos agrupados, `processes` contiene todos los del
+/// grupo (el primero hace de representante) y `children` los hijos de todos.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessNode {
+    pub processes: Vec<ProcessRow>,
+    pub totals: SubtreeTotals,
+    pub children: Vec<ProcessNode>,
+    /// Descendientes no incluidos por el límite de profundidad
+    pub hidden: usize,
+}
+
+impl ProcessNode {
+    pub fn process(&self) -> &ProcessRow {
+        &self.processes[0]
+    }
+
+    pub fn count(&self) -> usize {
+        self.processes.len()
+    }
+
+    /// Procesos del subárbol en preorden.
+    pub fn rows(&self) -> Vec<&ProcessRow> {
+        let mut rows: Vec<&ProcessRow> = self.processes.iter().collect();
+        for child in &self.children {
+            rows.extend(child.rows());
+        }
+        rows
+    }
+
+    /// Forma del subárbol: nombre y forma de los hijos, con sus repeticiones.
+    fn signature(&self) -> String {
+        let children: Vec<String> =
+            self.children.iter().map(|c| format!("{}*{}", c.count(), c.signature())).collect();
+        format!("{}({})[{}]", self.process().name, children.join(","), self.hidden)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FamilyTree {
+    pub roots: Vec<ProcessNode>,
+    /// Proceso consultado (ninguno con la vista completa)
+    pub target: Option<u32>,
+}
+
+impl FamilyTree {
+    pub fn rows(&self) -> Vec<&ProcessRow> {
+        self.roots.iter().flat_map(|r| r.rows()).collect()
+    }
+}
+
+fn children_map(rows: &[ProcessRow]) -> HashMap<u32, Vec<&ProcessRow>> {
+    let mut map: HashMap<u32, Vec<&ProcessRow>> = HashMap::new();
+    for row in rows {
+        if let Some(parent) = row.parent_pid.filter(|p| *p != row.pid) {
+            map.entry(parent).or_default().push(row);
+        }
+    }
+    for children in map.values_mut() {
+        children.sort_by_key(|r| r.pid);
+    }
+    map
+}
+
+/// Nodo con su subárbol completo para los totales, pero con hijos solo hasta `depth`.
+fn build_node(row: &ProcessRow, children: &HashMap<u32, Vec<&ProcessRow>>, depth: Option<usize>) -> ProcessNode {
+    let nodes: Vec<ProcessNode> = children
+        .get(&row.pid)
+        .into_iter()
+        .flatten()
+        .map(|child| build_node(child, children, depth.map(|d| d.saturating_sub(1))))
+        .collect();
+
+    let mut totals = SubtreeTotals::of(row);
+    for node in &nodes {
+        totals.add(&node.totals);
+        totals.descendants += 1;
+    }
+    let (children, hidden) = if depth == Some(0) { (Vec::new(), totals.descendants) } else { (nodes, 0) };
+    ProcessNode { processes: vec![row.clone()], totals, children, hidden }
+}
+
+/// Suma el grupo `other` (de la misma forma) al nodo.
+fn merge(node: &mut ProcessNode, other: ProcessNode) {
+    node.processes.extend(other.processes);
+    node.totals.add(&other.totals);
+    node.hidden += other.hidden;
+    let mut pending = other.children;
+    for child in node.children.iter_mut() {
+        let signature = child.signature();
+        if let Some(i) = pending.iter().position(|c| c.signature() == signature) {
+            merge(child, pending.remove(i));
+        }
+    }
+}
+
+/// Agrupa, de abajo arriba, los hermanos con la misma forma de subárbol.
+fn collapse(nodes: Vec<ProcessNode>) -> Vec<ProcessNode> {
+    let mut groups: Vec<(String, ProcessNode)> = Vec::new();
+    for mut node in nodes {
+        node.children = collapse(node.children);
+        let signature = node.signature();
+        match groups.iter_mut().find(|(s, _)| *s == signature) {
+            Some((_, group)) => merge(group, node),
+            None => groups.push((signature, node)),
+        }
+    }
+    groups.into_iter().map(|(_, node)| node).collect()
+}
+
+/// Construye la vista pedida a partir del estado de todos los procesos.
+pub fn build_family_tree(rows: &[ProcessRow], options: &FamilyOptions) -> CoreResult<FamilyTree> {
+    let by_pid: HashMap<u32, &ProcessRow> = rows.iter().map(|r| (r.pid, r)).collect();
+    let children = children_map(rows);
+
+    let (roots, target) = match options.view {
+        FamilyView::Descendants(pid) => {
+            let row = by_pid.get(&pid).ok_or(CoreError::ProcessNotFound(pid))?;
+            (vec![build_node(row, &children, options.depth)], Some(pid))
+        }
+        FamilyView::All => {
+            // Raíces: procesos sin padre o cuyo padre no aparece (p. ej. fuera del espacio de PIDs)
+            let mut roots: Vec<&ProcessRow> = rows
+                .iter()
+                .filter(|r| r.parent_pid.is_none_or(|p| p == r.pid || !by_pid.contains_key(&p)))
+                .collect();
+            roots.sort_by_key(|r| r.pid);
+            (roots.into_iter().map(|r| build_node(r, &children, options.depth)).collect(), None)
+        }
+        FamilyView::Ancestors(pid) => {
+            let row = by_pid.get(&pid).ok_or(CoreError::ProcessNotFound(pid))?;
+            let mut chain = vec![*row];
+            let mut current = row.parent_pid;
+            while let Some(parent) = current.and_then(|p| by_pid.get(&p)) {
+                // Protección frente a ciclos (PID reutilizado durante la lectura)
+                if chain.iter().any(|r| r.pid == parent.pid) {
+                    break;
+                }
+                chain.push(parent);
+                current = parent.parent_pid;
+            }
+            if let Some(depth) = options.depth {
+                chain.truncate(depth + 1);
+            }
+
+            // Del más lejano al consultado, cada uno hijo del anterior; los
+            // totales son los de su subárbol completo
+            let mut node: Option<ProcessNode> = None;
+            for row in chain {
+                node = Some(ProcessNode {
+                    processes: vec![row.clone()],
+                    totals: build_node(row, &children, Some(0)).totals,
+                    children: node.into_iter().collect(),
+                    hidden: 0,
+                });
+            }
+            (node.into_iter().collect(), Some(pid))
+        }
+    };
+
+    // En la cadena de ancestros no hay hermanos que agrupar
+    let roots = if options.collapse && !matches!(options.view, FamilyView::Ancestors(_)) {
+        collapse(roots)
+    } else {
+        roots
+    };
+    Ok(FamilyTree { roots, target })
+}
+
+/// Lee el estado actual de los procesos (sin hilos) y construye la vista pedida.
+pub fn get_family_tree(options: &FamilyOptions) -> CoreResult<FamilyTree> {
+    let rows = ProcessReader::new().get_processes();
+    build_family_tree(&rows, options)
+}
@@ -0,0 +1,229 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    commands::sampling::scheduler::TickScheduler,
+    errors::{CoreError, CoreResult},
+    process::{procfs::pss_mb, reader::ProcessReader},
+    stats::{median, theil_sen, LinearFit},
+};
+
+/// Muestras mínimas tras el calentamiento para dar un veredicto.
+const MIN_SAMPLES: usize = 10;
+
+/// Memoria que se sigue: RSS (residente) o PSS (residente con las páginas
+/// compartidas repartidas entre quienes las usan; solo Linux).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryMetric {
+    Rss,
+    Pss,
+}
+
+impl FromStr for MemoryMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "rss" => Ok(MemoryMetric::Rss),
+            "pss" => Ok(MemoryMetric::Pss),
+            _ => Err(format!("Métrica de memoria desconocida: {} (use rss o pss)", s)),
+        }
+    }
+}
+
+impl fmt::Display for MemoryMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MemoryMetric::Rss => write!(f, "RSS"),
+            MemoryMetric::Pss => write!(f, "PSS"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LeakOptions {
+    pub pid: u32,
+    pub duration: Duration,
+    pub interval: Duration,
+    /// Tramo inicial que no entra en el ajuste (cachés, pools que se llenan…)
+    pub warmup: Duration,
+    pub metric: MemoryMetric,
+    /// Límite para la proyección; por defecto, la memoria total del sistema
+    pub limit_mb: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemorySample {
+    pub captured_at: DateTime<Utc>,
+    pub elapsed_secs: f64,
+    pub memory_mb: f64,
+    pub warmup: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeakVerdict {
+    Leak,
+    PossibleLeak,
+    NoLeak,
+    Inconclusive,
+}
+
+impl fmt::Display for LeakVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeakVerdict::Leak => write!(f, "fuga"),
+            LeakVerdict::PossibleLeak => write!(f, "posible fuga"),
+            LeakVerdict::NoLeak => write!(f, "sin fuga"),
+            LeakVerdict::Inconclusive => write!(f, "no concluyente"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeakReport {
+    pub pid: u32,
+    pub name: String,
+    pub metric: MemoryMetric,
+    pub started_at: DateTime<Utc>,
+    pub duration_secs: f64, // real: menor que la pedida si el proceso terminó antes
+    pub warmup_secs: f64,
+    pub exited_early: bool,
+    pub samples: Vec<MemorySample>,
+    pub analysed_samples: usize, // tras el calentamiento
+    pub start_mb: f64,           // según la tendencia, al principio y al final del tramo analizado
+    pub end_mb: f64,
+    pub growth_mb_per_hour: f64,
+    pub r_squared: f64,
+    pub limit_mb: f64,
+    pub time_to_limit_secs: Option<f64>, // sin definir si la memoria no crece
+    pub verdict: LeakVerdict,
+    pub reason: String,
+}
+
+/// Veredicto a partir de la tendencia: hace falta que la memoria crezca, que
+/// la recta explique bien la serie (R²) y que el crecimiento en la ventana
+/// analizada no sea despreciable frente al nivel del proceso.
+fn verdict(fit: &LinearFit, level_mb: f64, window_secs: f64, samples: usize) -> (LeakVerdict, String) {
+    if samples < MIN_SAMPLES {
+        return (
+            LeakVerdict::Inconclusive,
+            format!("Solo {} muestras tras el calentamiento (mínimo {})", samples, MIN_SAMPLES),
+        );
+    }
+    let growth = fit.slope * window_secs;
+    let relevant = |fraction: f64, floor_mb: f64| growth >= (level_mb * fraction).max(floor_mb);
+
+    if fit.slope > 0.0 && fit.r_squared >= 0.7 && relevant(0.01, 1.0) {
+        (
+            LeakVerdict::Leak,
+            format!("Crecimiento sostenido de {:.2} MB en la ventana (R² {:.2})", growth, fit.r_squared),
+        )
+    } else if fit.slope > 0.0 && fit.r_squared >= 0.3 && relevant(0.005, 0.5) {
+        (
+            LeakVerdict::PossibleLeak,
+            format!(
+                "Crece {:.2} MB en la ventana pero con mucha variación (R² {:.2}); conviene una ventana más larga",
+                growth, fit.r_squared
+            ),
+        )
+    } else if fit.slope > 0.0 && relevant(0.01, 1.0) {
+        (
+            LeakVerdict::NoLeak,
+            format!("La memoria sube {:.2} MB pero sin tendencia clara (R² {:.2})", growth, fit.r_squared),
+        )
+    } else {
+        (LeakVerdict::NoLeak, format!("Memoria estable (variación de {:.2} MB según la tendencia)", growth))
+    }
+}
+
+/// Muestrea la memoria de `pid` durante `duration` y ajusta una tendencia
+/// robusta (Theil-Sen) a las muestras posteriores al calentamiento. Si el
+/// proceso termina antes se analiza lo recogido. `on_sample` recibe cada
+/// muestra según se toma.
+pub fn check_leak(options: &LeakOptions, mut on_sample: impl FnMut(&MemorySample)) -> CoreResult<LeakReport> {
+    if options.interval.is_zero() {
+        return Err(CoreError::ValidationError("El intervalo debe ser de al menos 1 ms".into()));
+    }
+    if options.warmup >= options.duration {
+        return Err(CoreError::ValidationError("El calentamiento debe ser menor que la duración".into()));
+    }
+    if options.metric == MemoryMetric::Pss && pss_mb(options.pid).is_none() {
+        return Err(CoreError::ValidationError(format!(
+            "No se puede leer la PSS del proceso {} (requiere Linux 4.14+ y permisos sobre el proceso)",
+            options.pid
+        )));
+    }
+
+    let mut reader = ProcessReader::new();
+    let first = reader
+        .current_processes()
+        .into_iter()
+        .find(|r| r.pid == options.pid && r.state != "Zombie")
+        .ok_or(CoreError::ProcessNotFound(options.pid))?;
+    let limit_mb = options.limit_mb.unwrap_or(reader.system().total_memory() as f64 / 1_048_576.0);
+
+    let started_at = Utc::now();
+    let warmup_secs = options.warmup.as_secs_f64();
+    let mut scheduler = TickScheduler::new(options.interval);
+    let mut samples: Vec<MemorySample> = Vec::new();
+    let mut exited_early = false;
+    let clock = std::time::Instant::now();
+
+    while clock.elapsed() < options.duration {
+        scheduler.wait();
+        reader.refresh_processes();
+        let elapsed_secs = clock.elapsed().as_secs_f64();
+
+        // El mismo proceso: un PID reutilizado tendría otro instante de arranque
+        let row = reader
+            .current_processes()
+            .into_iter()
+            .find(|r| r.pid == options.pid && r.start_time == first.start_time && r.state != "Zombie");
+        let memory_mb = match (row, options.metric) {
+            (Some(row), MemoryMetric::Rss) => Some(row.memory_mb),
+            (Some(_), MemoryMetric::Pss) => pss_mb(options.pid),
+            (None, _) => None,
+        };
+        let Some(memory_mb) = memory_mb else {
+            exited_early = true;
+            break;
+        };
+
+        let sample = MemorySample { captured_at: Utc::now(), elapsed_secs, memory_mb, warmup: elapsed_secs < warmup_secs };
+        on_sample(&sample);
+        samples.push(sample);
+    }
+
+    let (x, y): (Vec<f64>, Vec<f64>) =
+        samples.iter().filter(|s| !s.warmup).map(|s| (s.elapsed_secs, s.memory_mb)).unzip();
+    let fit = theil_sen(&x, &y);
+    let (window_start, window_end) = (x.first().copied().unwrap_or(0.0), x.last().copied().unwrap_or(0.0));
+    let (start_mb, end_mb) = if x.is_empty() { (0.0, 0.0) } else { (fit.predict(window_start), fit.predict(window_end)) };
+    let (verdict, reason) = verdict(&fit, median(&y), window_end - window_start, x.len());
+
+    Ok(LeakReport {
+        pid: options.pid,
+        name: first.name,
+        metric: options.metric,
+        started_at,
+        duration_secs: clock.elapsed().as_secs_f64(),
+        warmup_secs,
+        exited_early,
+        analysed_samples: x.len(),
+        start_mb,
+        end_mb,
+        growth_mb_per_hour: fit.slope * 3600.0,
+        r_squared: fit.r_squared,
+        limit_mb,
+        time_to_limit_secs: (fit.slope > 0.0 && !x.is_empty()).then(|| ((limit_mb - end_mb) / fit.slope).max(0.0)),
+        verdict,
+        reason,
+        samples,
+    })
+}
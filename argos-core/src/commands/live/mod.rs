@@ -1,23 +1,136 @@
-use std::{thread, time::Duration};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
 use crate::{
+    commands::sampling::{recorder::SampleRecorder, scheduler::TickScheduler},
     errors::{CoreError, CoreResult},
-    process::{model::ProcessRow, reader::ProcessReader},
+    process::{filter::matches_pattern, model::{ProcessRow, SampleRecord}, procfs::cpu_time_ms, reader::ProcessReader},
 };
 
-/// Monitorea en vivo un proceso por PID, devolviendo un vector de ProcessRow con cada muestreo.
-/// El callback se llama en cada iteración con el ProcessRow actualizado.
-pub fn monitor_live_by_pid<F>(pid: u32, mut callback: F) -> CoreResult<()>
+/// Qué procesos seguir en vivo. Con `follow_children` se añaden solos los
+/// procesos que nacen de uno seguido (y sus descendientes).
+#[derive(Debug, Clone, Default)]
+pub struct LiveTargets {
+    pub pids: Vec<u32>,
+    pub names: Vec<String>,
+    pub follow_children: bool,
+}
+
+/// Proceso seguido que terminó.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveExit {
+    pub pid: u32,
+    pub name: String,
+    pub exited_at: DateTime<Utc>, // primera iteración en que ya no estaba
+}
+
+/// Una iteración: una muestra por proceso seguido y los que terminaron desde la anterior.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveTick {
+    pub captured_at: DateTime<Utc>,
+    pub samples: Vec<SampleRecord>,
+    pub exited: Vec<LiveExit>,
+}
+
+struct Watched {
+    start_time: u64, // junto al PID identifica el proceso (los PIDs se reutilizan)
+    name: String,
+}
+
+/// Procesos que entran en seguimiento en esta iteración: los pedidos por PID
+/// (solo al principio), los que coinciden con un nombre y, si se siguen hijos,
+/// los descendientes de cualquier seguido.
+fn add_new(targets: &LiveTargets, rows: &[ProcessRow], watched: &mut HashMap<u32, Watched>, first: bool) {
+    let alive = rows.iter().filter(|r| r.state != "Zombie");
+    for row in alive.clone() {
+        let wanted = (first && targets.pids.contains(&row.pid)) || targets.names.iter().any(|n| matches_pattern(n, &row.name));
+        if wanted {
+            watched.entry(row.pid).or_insert_with(|| Watched { start_time: row.start_time, name: row.name.clone() });
+        }
+    }
+    if !targets.follow_children {
+        return;
+    }
+
+    // Hasta que no aparezca ninguno nuevo: así entran también nietos, bisnietos…
+    loop {
+        let new: Vec<&ProcessRow> = alive
+            .clone()
+            .filter(|r| !watched.contains_key(&r.pid))
+            .filter(|r| r.parent_pid.is_some_and(|p| watched.contains_key(&p)))
+            .collect();
+        if new.is_empty() {
+            break;
+        }
+        for row in new {
+            watched.insert(row.pid, Watched { start_time: row.start_time, name: row.name.clone() });
+        }
+    }
+}
+
+/// Sigue en vivo varios procesos, con una iteración cada `interval`. Los que
+/// terminan se notifican en `exited` y se sigue con el resto. Termina cuando
+/// no queda ninguno y no hay patrones de nombre con los que encontrar más.
+pub fn monitor_live<F>(targets: &LiveTargets, interval: Duration, mut callback: F) -> CoreResult<()>
 where
-    F: FnMut(&ProcessRow),
+    F: FnMut(&LiveTick),
 {
+    if targets.pids.is_empty() && targets.names.is_empty() {
+        return Err(CoreError::ValidationError("Indique al menos un PID o un nombre de proceso".into()));
+    }
+    if interval.is_zero() {
+        return Err(CoreError::ValidationError("El intervalo debe ser de al menos 1 ms".into()));
+    }
+
+    // El lector se conserva para que la CPU se mida sobre el último intervalo
     let mut reader = ProcessReader::new();
+    let mut scheduler = TickScheduler::new(interval);
+    let mut recorder = SampleRecorder::new();
+    let mut watched: HashMap<u32, Watched> = HashMap::new();
+    let mut first = true;
+
     loop {
-        let rows = reader.get_by_pids(&[pid]);
-        if let Some(row) = rows.into_iter().next() {
-            callback(&row);
-            thread::sleep(Duration::from_secs(1));
-        } else {
-            return Err(CoreError::ProcessNotFound(pid));
+        scheduler.wait();
+        reader.refresh_processes();
+        let tick = recorder.tick();
+        let rows = reader.current_processes();
+
+        // Los seguidos que ya no están (o cuyo PID ahora es de otro proceso) terminaron
+        let alive: HashSet<(u32, u64)> =
+            rows.iter().filter(|r| r.state != "Zombie").map(|r| (r.pid, r.start_time)).collect();
+        let mut exited: Vec<LiveExit> = watched
+            .iter()
+            .filter(|(pid, w)| !alive.contains(&(**pid, w.start_time)))
+            .map(|(pid, w)| LiveExit { pid: *pid, name: w.name.clone(), exited_at: tick.captured_at })
+            .collect();
+        exited.sort_by_key(|e| e.pid);
+        for exit in &exited {
+            watched.remove(&exit.pid);
+        }
+
+        add_new(targets, &rows, &mut watched, first);
+        if first && watched.is_empty() {
+            return match targets.pids.as_slice() {
+                [pid] if targets.names.is_empty() => Err(CoreError::ProcessNotFound(*pid)),
+                _ => Err(CoreError::ProcessNotFoundList(targets.pids.clone())),
+            };
+        }
+        first = false;
+
+        let mut selected: Vec<&ProcessRow> = rows.iter().filter(|r| watched.contains_key(&r.pid)).collect();
+        selected.sort_by_key(|r| r.pid);
+        let samples = selected
+            .into_iter()
+            .map(|row| recorder.record(&tick, row.clone(), cpu_time_ms(row.pid)))
+            .collect();
+
+        callback(&LiveTick { captured_at: tick.captured_at, samples, exited });
+
+        if watched.is_empty() && targets.names.is_empty() {
+            return Ok(());
         }
     }
-}
\ No newline at end of file
+}
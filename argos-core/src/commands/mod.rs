@@ -6,6 +6,14 @@ pub mod history;
 pub mod live;
 pub mod list;
 pub mod kill;
+pub mod renice;
 pub mod watchdog;
 pub mod types;
-pub mod family;
\ No newline at end of file
+pub mod family;
+pub mod alerts;
+pub mod snapshot;
+pub mod run;
+pub mod bench;
+pub mod top;
+pub mod zombies;
+pub mod leak;
@@ -0,0 +1,22 @@
+use crate::errors::{CoreError, CoreResult};
+
+/// Cambia la prioridad (nice, de -20 a 19) de un proceso.
+#[cfg(unix)]
+pub fn renice_process(pid: u32, nice: i32) -> CoreResult<()> {
+    // El tipo de `which` depende de la libc (u32 en glibc, i32 en el resto)
+    let result = unsafe { libc::setpriority(libc::PRIO_PROCESS as _, pid as libc::id_t, nice) };
+    if result == 0 {
+        Ok(())
+    } else {
+        let err = std::io::Error::last_os_error();
+        match err.raw_os_error() {
+            Some(libc::ESRCH) => Err(CoreError::ProcessNotFound(pid)),
+            _ => Err(CoreError::Other(format!("No se pudo cambiar la prioridad del proceso {}: {}", pid, err))),
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub fn renice_process(pid: u32, _nice: i32) -> CoreResult<()> {
+    Err(CoreError::Other(format!("renice no está soportado en esta plataforma (PID {})", pid)))
+}
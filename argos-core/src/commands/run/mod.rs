@@ -0,0 +1,211 @@
+use std::collections::{HashMap, HashSet};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    commands::sampling::{
+        aggregate_subtree, recorder::SampleRecorder, scheduler::{ScheduleReport, TickScheduler}, SampleRun,
+    },
+    errors::{CoreError, CoreResult},
+    process::{model::SampleRecord, procfs::peak_rss_mb, reader::ProcessReader},
+    stats::mean,
+};
+
+const EXIT_POLL: Duration = Duration::from_millis(5);
+
+/// Resumen de una ejecución, al estilo de `/usr/bin/time -v`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunSummary {
+    pub command: String,
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>, // señal que terminó el proceso (solo unix)
+    pub wall_secs: f64,
+    pub user_cpu_secs: Option<f64>,
+    pub system_cpu_secs: Option<f64>,
+    pub peak_rss_mb: f64,
+    pub avg_cpu: f64,
+    pub read_bytes: u64,
+    pub written_bytes: u64,
+    pub children: usize, // descendientes vistos en alguna muestra
+    pub samples: usize,
+}
+
+impl RunSummary {
+    /// Código con el que terminar para imitar al comando (128 + señal si lo mató una).
+    pub fn exit_status(&self) -> i32 {
+        match (self.exit_code, self.signal) {
+            (Some(code), _) => code,
+            (None, Some(signal)) => 128 + signal,
+            (None, None) => 1,
+        }
+    }
+}
+
+/// Perfil completo: el resumen y la serie de muestras del árbol del comando.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunProfile {
+    pub summary: RunSummary,
+    pub samples: Vec<SampleRecord>,
+    pub schedule: Option<ScheduleReport>,
+}
+
+impl RunProfile {
+    /// La serie como muestreo, para guardarla o exportarla igual que `sample`.
+    pub fn to_sample_run(&self) -> SampleRun {
+        SampleRun { samples: self.samples.clone(), exited: Vec::new(), schedule: self.schedule.clone() }
+    }
+}
+
+/// Estado final del hijo y, en unix, los recursos que consumió él y los
+/// descendientes que esperó.
+struct Finished {
+    exit_code: Option<i32>,
+    signal: Option<i32>,
+    user_cpu_secs: Option<f64>,
+    system_cpu_secs: Option<f64>,
+    max_rss_mb: Option<f64>,
+}
+
+#[cfg(unix)]
+fn try_finish(child: &mut Child) -> CoreResult<Option<Finished>> {
+    let mut status = 0;
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    // wait4 recoge al hijo y devuelve su rusage, que incluye a los descendientes que él esperó
+    let pid = unsafe { libc::wait4(child.id() as libc::pid_t, &mut status, libc::WNOHANG, &mut usage) };
+    if pid < 0 {
+        return Err(CoreError::Io(std::io::Error::last_os_error()));
+    }
+    if pid == 0 {
+        return Ok(None);
+    }
+
+    let secs = |t: libc::timeval| t.tv_sec as f64 + t.tv_usec as f64 / 1e6;
+    Ok(Some(Finished {
+        exit_code: libc::WIFEXITED(status).then(|| libc::WEXITSTATUS(status)),
+        signal: libc::WIFSIGNALED(status).then(|| libc::WTERMSIG(status)),
+        user_cpu_secs: Some(secs(usage.ru_utime)),
+        system_cpu_secs: Some(secs(usage.ru_stime)),
+        // ru_maxrss viene en KB en Linux
+        max_rss_mb: Some(usage.ru_maxrss as f64 / 1024.0),
+    }))
+}
+
+#[cfg(not(unix))]
+fn try_finish(child: &mut Child) -> CoreResult<Option<Finished>> {
+    Ok(child.try_wait()?.map(|status| Finished {
+        exit_code: status.code(),
+        signal: None,
+        user_cpu_secs: None,
+        system_cpu_secs: None,
+        max_rss_mb: None,
+    }))
+}
+
+/// Lanza `command` y muestrea su árbol de procesos (sin sus hilos) cada
+/// `interval_ms` hasta que termina. La entrada y salida estándar del comando
+/// son las de Argos, salvo con `silent`, que descarta su salida.
+pub fn profile_command(command: &[String], interval_ms: u64, silent: bool) -> CoreResult<RunProfile> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| CoreError::ValidationError("Indique el comando a ejecutar".into()))?;
+    if interval_ms == 0 {
+        return Err(CoreError::ValidationError("El intervalo debe ser de al menos 1 ms".into()));
+    }
+
+    // El lector se prepara antes de lanzar el comando: crearlo tarda más que
+    // algunos comandos cortos
+    let mut reader = ProcessReader::new();
+    let started = Instant::now();
+    let mut process = Command::new(program);
+    process.args(args);
+    if silent {
+        process.stdout(Stdio::null()).stderr(Stdio::null());
+    }
+    let mut child = process
+        .spawn()
+        .map_err(|e| CoreError::Other(format!("No se pudo ejecutar '{}': {}", program, e)))?;
+    let pid = child.id();
+
+    let mut scheduler = TickScheduler::new(Duration::from_millis(interval_ms));
+    let mut recorder = SampleRecorder::new();
+    let mut samples = Vec::new();
+    let mut descendants: HashSet<(u32, u64)> = HashSet::new();
+    // Últimos totales de disco de cada proceso del árbol: sobreviven a los que terminan
+    let mut disk: HashMap<(u32, u64), (f64, f64)> = HashMap::new();
+    // Pico propio de cualquier proceso del árbol, que capta picos entre muestras
+    let mut process_peak: f64 = 0.0;
+
+    // Entre muestras se comprueba a menudo si terminó, para no alargar el tiempo medido
+    let mut finished = None;
+    let finished = loop {
+        scheduler.wait_unless(EXIT_POLL, || {
+            // El pico propio se consulta también aquí para no perderlo en comandos muy cortos
+            process_peak = process_peak.max(peak_rss_mb(pid).unwrap_or(0.0));
+            finished = try_finish(&mut child).transpose();
+            finished.is_some()
+        });
+        if finished.is_none() {
+            finished = try_finish(&mut child).transpose();
+        }
+        if let Some(result) = finished.take() {
+            break result?;
+        }
+        reader.refresh_processes();
+        let rows = reader.current_processes();
+
+        let mut children: HashMap<u32, Vec<usize>> = HashMap::new();
+        for (i, row) in rows.iter().enumerate() {
+            if let Some(parent) = row.parent_pid {
+                children.entry(parent).or_default().push(i);
+            }
+        }
+        // Ya terminado pero aún sin recoger: no queda nada que medir
+        let Some(root) = rows.iter().find(|r| r.pid == pid && r.state != "Zombie") else { continue };
+
+        let (row, cpu_time, members) = aggregate_subtree(root, &rows, &children);
+        for member in members.iter().filter_map(|m| rows.iter().find(|r| r.pid == *m)) {
+            let key = (member.pid, member.start_time);
+            if member.pid != pid {
+                descendants.insert(key);
+            }
+            disk.insert(key, (member.read_disk_usage, member.write_disk_usage));
+            process_peak = process_peak.max(peak_rss_mb(member.pid).unwrap_or(0.0));
+        }
+        let tick = recorder.tick();
+        samples.push(recorder.record(&tick, row, cpu_time));
+    };
+    let wall_secs = started.elapsed().as_secs_f64();
+
+    // ru_maxrss cuenta también la memoria de Argos que el hijo tenía antes del
+    // `exec`: solo es del comando si supera el pico del propio Argos
+    let own_peak = peak_rss_mb(std::process::id()).unwrap_or(f64::INFINITY);
+    if let Some(max_rss) = finished.max_rss_mb.filter(|m| *m > own_peak) {
+        process_peak = process_peak.max(max_rss);
+    }
+
+    let sampled_peak = samples.iter().map(|s| s.process.memory_mb).fold(0.0, f64::max);
+    let to_bytes = |mb: f64| (mb * 1_048_576.0).round() as u64;
+    let cpu: Vec<f64> = samples.iter().skip(1).map(|s| s.process.cpu_usage).collect();
+    let summary = RunSummary {
+        command: command.join(" "),
+        exit_code: finished.exit_code,
+        signal: finished.signal,
+        wall_secs,
+        user_cpu_secs: finished.user_cpu_secs,
+        system_cpu_secs: finished.system_cpu_secs,
+        peak_rss_mb: sampled_peak.max(process_peak),
+        // Con el tiempo de CPU real el promedio no depende de que las muestras caigan en picos
+        avg_cpu: match (finished.user_cpu_secs, finished.system_cpu_secs) {
+            (Some(user), Some(system)) if wall_secs > 0.0 => (user + system) / wall_secs * 100.0,
+            _ => mean(&cpu),
+        },
+        read_bytes: disk.values().map(|(read, _)| to_bytes(*read)).sum(),
+        written_bytes: disk.values().map(|(_, written)| to_bytes(*written)).sum(),
+        children: descendants.len(),
+        samples: samples.len(),
+    };
+
+    Ok(RunProfile { summary, samples, schedule: Some(scheduler.report()) })
+}
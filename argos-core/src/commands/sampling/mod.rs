@@ -1,30 +1,259 @@
-use std::{thread, time::Duration};
+pub mod recorder;
+pub mod scheduler;
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
 use crate::{
+    db::{log_session::insert_log_session, manager::connect, process::replace_process, sample::insert_sample},
     errors::{CoreError, CoreResult},
-    process::{model::ProcessRow, reader::ProcessReader},
+    models::{NewLogSession, Process, Sample},
+    process::{
+        filter::matches_pattern,
+        model::{ProcessRow, SampleRecord},
+        procfs::cpu_time_ms,
+        reader::ProcessReader,
+    },
 };
+use recorder::SampleRecorder;
+use scheduler::{ScheduleReport, TickScheduler};
 
-/// Muestrea un único proceso durante varias iteraciones a intervalos fijos.
-pub fn sample_process(pid: u32, iterations: usize, interval_ms: u64) -> CoreResult<Vec<ProcessRow>> {
+/// Qué procesos muestrear: PIDs concretos y/o patrones de nombre (con `*`).
+/// Con `include_children` cada proceso se muestrea junto a todos sus
+/// descendientes, sumados en una sola serie.
+#[derive(Debug, Clone, Default)]
+pub struct SampleTargets {
+    pub pids: Vec<u32>,
+    pub names: Vec<String>,
+    pub include_children: bool,
+}
+
+/// Proceso muestreado que terminó antes de acabar el muestreo.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessExit {
+    pub pid: u32,
+    pub name: String,
+    pub last_seen: DateTime<Utc>,
+    pub exited_at: DateTime<Utc>, // primera iteración en que ya no estaba
+    pub samples: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SampleRun {
+    pub samples: Vec<SampleRecord>,
+    #[serde(default)]
+    pub exited: Vec<ProcessExit>,
+    #[serde(default)]
+    pub schedule: Option<ScheduleReport>,
+}
+
+struct Tracked {
+    start_time: u64, // junto al PID identifica el proceso (los PIDs se reutilizan)
+    name: String,
+    last_seen: DateTime<Utc>,
+    samples: usize,
+}
+
+/// Suma a la fila del proceso las de todos sus descendientes, junto con el
+/// tiempo de CPU del conjunto y los PIDs que lo forman.
+/// `rows` no debe incluir hilos: contarían como hijos y sumarían la memoria
+/// del proceso una vez por hilo.
+pub(crate) fn aggregate_subtree(
+    root: &ProcessRow,
+    rows: &[ProcessRow],
+    children: &HashMap<u32, Vec<usize>>,
+) -> (ProcessRow, Option<u64>, Vec<u32>) {
+    let mut total = root.clone();
+    let mut cpu_time = cpu_time_ms(root.pid);
+    let mut members = vec![root.pid];
+    let mut pending: Vec<u32> = vec![root.pid];
+    while let Some(pid) = pending.pop() {
+        for &i in children.get(&pid).into_iter().flatten() {
+            let child = &rows[i];
+            total.cpu_usage += child.cpu_usage;
+            total.memory_mb += child.memory_mb;
+            total.virtual_memory_mb += child.virtual_memory_mb;
+            total.read_disk_usage += child.read_disk_usage;
+            total.write_disk_usage += child.write_disk_usage;
+            cpu_time = cpu_time.map(|t| t + cpu_time_ms(child.pid).unwrap_or(0));
+            members.push(child.pid);
+            pending.push(child.pid);
+        }
+    }
+    (total, cpu_time, members)
+}
+
+/// Procesos del estado actual que corresponden a los objetivos. Los zombis ya
+/// terminaron y no cuentan. Con `include_children` se descartan los que ya
+/// cuentan como descendientes de otro.
+fn select_roots<'a>(targets: &SampleTargets, rows: &'a [ProcessRow]) -> Vec<&'a ProcessRow> {
+    let mut selected: Vec<&ProcessRow> = rows
+        .iter()
+        .filter(|r| r.state != "Zombie")
+        .filter(|r| targets.pids.contains(&r.pid) || targets.names.iter().any(|n| matches_pattern(n, &r.name)))
+        .collect();
+    selected.sort_by_key(|r| r.pid);
+    if !targets.include_children {
+        return selected;
+    }
+
+    let parents: HashMap<u32, Option<u32>> = rows.iter().map(|r| (r.pid, r.parent_pid)).collect();
+    let pids: HashSet<u32> = selected.iter().map(|r| r.pid).collect();
+    selected
+        .into_iter()
+        .filter(|r| {
+            let mut current = r.parent_pid;
+            while let Some(pid) = current {
+                if pids.contains(&pid) {
+                    return false;
+                }
+                current = parents.get(&pid).copied().flatten();
+            }
+            true
+        })
+        .collect()
+}
+
+/// Muestrea varios procesos durante `iterations` iteraciones, a intervalos
+/// medidos desde el inicio y no desde el final de cada lectura. Los procesos que
+/// terminan a mitad se registran en `exited` y el muestreo sigue con el resto;
+/// los que aparecen después y coinciden con un patrón de nombre se incorporan.
+/// Termina antes de tiempo si ya no queda ningún proceso que muestrear.
+pub fn sample_targets(targets: &SampleTargets, iterations: usize, interval_ms: u64) -> CoreResult<SampleRun> {
     if iterations == 0 {
         return Err(CoreError::ValidationError("El número de iteraciones debe ser mayor que 0".into()));
     }
+    if interval_ms == 0 {
+        return Err(CoreError::ValidationError("El intervalo debe ser de al menos 1 ms".into()));
+    }
+    if targets.pids.is_empty() && targets.names.is_empty() {
+        return Err(CoreError::ValidationError("Indique al menos un PID o un nombre de proceso".into()));
+    }
+
+    // El lector se conserva entre iteraciones para que la CPU se mida sobre el
+    // intervalo; su primera lectura sirve de referencia para la primera muestra
+    let mut reader = ProcessReader::new();
+    let mut scheduler = TickScheduler::new(Duration::from_millis(interval_ms));
+    let mut recorder = SampleRecorder::new();
+    let mut tracked: HashMap<u32, Tracked> = HashMap::new();
+    let mut run = SampleRun { samples: Vec::with_capacity(iterations), exited: Vec::new(), schedule: None };
+
+    for iteration in 0..iterations {
+        scheduler.wait();
+        reader.refresh_processes();
+        let tick = recorder.tick();
+        let now = tick.captured_at;
+        let rows = reader.current_processes();
+
+        let mut children: HashMap<u32, Vec<usize>> = HashMap::new();
+        if targets.include_children {
+            for (i, row) in rows.iter().enumerate() {
+                if let Some(parent) = row.parent_pid {
+                    children.entry(parent).or_default().push(i);
+                }
+            }
+        }
 
-    let mut results = Vec::with_capacity(iterations);
+        let roots = select_roots(targets, &rows);
+        if iteration == 0 && roots.is_empty() {
+            return match targets.pids.as_slice() {
+                [pid] if targets.names.is_empty() => Err(CoreError::ProcessNotFound(*pid)),
+                _ => Err(CoreError::ProcessNotFoundList(targets.pids.clone())),
+            };
+        }
 
-    for _ in 0..iterations {
-        let mut reader = ProcessReader::new();
-        let rows = reader.get_by_pids(&[pid]);
+        // Los seguidos que ya no están (o cuyo PID ahora es de otro proceso) terminaron
+        let alive: HashSet<(u32, u64)> = roots.iter().map(|r| (r.pid, r.start_time)).collect();
+        let gone: Vec<u32> = tracked
+            .iter()
+            .filter(|(pid, t)| !alive.contains(&(**pid, t.start_time)))
+            .map(|(pid, _)| *pid)
+            .collect();
+        for pid in gone {
+            if let Some(t) = tracked.remove(&pid) {
+                run.exited.push(ProcessExit { pid, name: t.name, last_seen: t.last_seen, exited_at: now, samples: t.samples });
+            }
+        }
+
+        for root in roots {
+            let (row, cpu_time) = if targets.include_children {
+                let (row, cpu_time, _) = aggregate_subtree(root, &rows, &children);
+                (row, cpu_time)
+            } else {
+                (root.clone(), cpu_time_ms(root.pid))
+            };
+            let entry = tracked.entry(root.pid).or_insert_with(|| Tracked {
+                start_time: root.start_time,
+                name: root.name.clone(),
+                last_seen: now,
+                samples: 0,
+            });
+            entry.last_seen = now;
+            entry.samples += 1;
+            run.samples.push(recorder.record(&tick, row, cpu_time));
+        }
 
-        if rows.is_empty() {
-            return Err(CoreError::ProcessNotFound(pid));
+        // Solo con PIDs fijos no puede aparecer nada nuevo
+        if tracked.is_empty() && targets.names.is_empty() {
+            break;
         }
+    }
+
+    run.schedule = Some(scheduler.report());
+    Ok(run)
+}
+
+/// Muestrea un único proceso durante varias iteraciones a intervalos fijos.
+pub fn sample_process(pid: u32, iterations: usize, interval_ms: u64) -> CoreResult<Vec<ProcessRow>> {
+    let targets = SampleTargets { pids: vec![pid], ..Default::default() };
+    Ok(sample_targets(&targets, iterations, interval_ms)?.samples.into_iter().map(|s| s.process).collect())
+}
 
-        results.push(rows[0].clone()); // Solo un proceso, así que tomamos el primero
-        if iterations > 1 {
-            thread::sleep(Duration::from_millis(interval_ms));
+/// Guarda el muestreo en la base de datos: una sesión por proceso con sus
+/// muestras. Devuelve los IDs de las sesiones creadas.
+pub fn save_sample_run(database_url: Option<&str>, run: &SampleRun) -> CoreResult<Vec<String>> {
+    let mut conn = connect(database_url)?;
+    let mut pids: Vec<u32> = Vec::new();
+    for sample in &run.samples {
+        if !pids.contains(&sample.process.pid) {
+            pids.push(sample.process.pid);
         }
     }
 
-    Ok(results)
+    let mut sessions = Vec::new();
+    for pid in pids {
+        let samples: Vec<&SampleRecord> = run.samples.iter().filter(|s| s.process.pid == pid).collect();
+        let (first, last) = (samples[0], samples[samples.len() - 1]);
+        let id = format!("{}-{}", pid, first.captured_at.format("%Y%m%d%H%M%S%3f"));
+
+        replace_process(
+            &mut conn,
+            &Process {
+                pid: pid as i32,
+                name: last.process.name.clone(),
+                state: last.process.state.clone(),
+                memory_mb: Some(last.process.memory_mb as f32),
+                start_time: Some(last.process.start_time as i32),
+                parent_pid: last.process.parent_pid.map(|p| p as i32),
+            },
+        )?;
+        insert_log_session(
+            &mut conn,
+            &NewLogSession {
+                id: &id,
+                process_pid: pid as i32,
+                started_at: Some(first.captured_at.naive_utc()),
+                duration_secs: Some((last.elapsed_secs - first.elapsed_secs).round() as i32),
+                iterations: Some(samples.len() as i32),
+            },
+        )?;
+        for sample in &samples {
+            insert_sample(&mut conn, &Sample::from_record(&id, first, sample))?;
+        }
+        sessions.push(id);
+    }
+    Ok(sessions)
 }
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+
+use crate::process::model::{ProcessRow, SampleRecord};
+
+/// Segundos del reloj monótono del sistema (`CLOCK_MONOTONIC`): comparables
+/// entre procesos del mismo arranque e inmunes a cambios de hora.
+#[cfg(unix)]
+pub fn monotonic_secs() -> f64 {
+    let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts) };
+    ts.tv_sec as f64 + ts.tv_nsec as f64 / 1e9
+}
+
+/// Sin reloj del sistema accesible: segundos desde la primera llamada.
+#[cfg(not(unix))]
+pub fn monotonic_secs() -> f64 {
+    static ORIGIN: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
+    ORIGIN.get_or_init(Instant::now).elapsed().as_secs_f64()
+}
+
+/// Instante de una iteración, común a todas las muestras tomadas en ella.
+#[derive(Debug, Clone, Copy)]
+pub struct Tick {
+    pub captured_at: DateTime<Utc>,
+    pub monotonic_secs: f64,
+    pub elapsed_secs: f64,
+}
+
+struct Previous {
+    start_time: u64,
+    elapsed_secs: f64,
+    cpu_time_ms: Option<u64>,
+    read_mb: f64,
+    write_mb: f64,
+}
+
+/// Convierte lecturas de procesos en [`SampleRecord`]s, con el tiempo de
+/// captura y los incrementos respecto a la muestra anterior de cada PID.
+pub struct SampleRecorder {
+    started: Option<Instant>, // primera iteración: origen de `elapsed_secs`
+    previous: HashMap<u32, Previous>,
+}
+
+impl Default for SampleRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SampleRecorder {
+    pub fn new() -> Self {
+        Self { started: None, previous: HashMap::new() }
+    }
+
+    pub fn tick(&mut self) -> Tick {
+        let started = *self.started.get_or_insert_with(Instant::now);
+        Tick {
+            captured_at: Utc::now(),
+            monotonic_secs: monotonic_secs(),
+            elapsed_secs: started.elapsed().as_secs_f64(),
+        }
+    }
+
+    /// Registra la muestra de un proceso. Si el PID pasó a otro proceso (otro
+    /// instante de arranque) no se calculan incrementos. Con el tiempo de CPU
+    /// de ambas muestras el % de CPU se recalcula a partir de él, más preciso
+    /// que el de sysinfo en intervalos cortos.
+    pub fn record(&mut self, tick: &Tick, mut process: ProcessRow, cpu_time_ms: Option<u64>) -> SampleRecord {
+        let to_bytes = |mb: f64| (mb.max(0.0) * 1_048_576.0).round() as u64;
+        let previous = self.previous.get(&process.pid).filter(|p| p.start_time == process.start_time);
+        let interval_secs = previous.map(|p| tick.elapsed_secs - p.elapsed_secs);
+        let cpu_time_delta_ms = previous.and_then(|p| Some(cpu_time_ms?.saturating_sub(p.cpu_time_ms?)));
+        if let (Some(delta), Some(interval)) = (cpu_time_delta_ms, interval_secs) {
+            if interval > 0.0 {
+                process.cpu_usage = delta as f64 / (interval * 1000.0) * 100.0;
+            }
+        }
+
+        let record = SampleRecord {
+            captured_at: tick.captured_at,
+            monotonic_secs: tick.monotonic_secs,
+            elapsed_secs: tick.elapsed_secs,
+            interval_secs,
+            cpu_time_ms,
+            cpu_time_delta_ms,
+            read_bytes_delta: previous.map(|p| to_bytes(process.read_disk_usage - p.read_mb)),
+            write_bytes_delta: previous.map(|p| to_bytes(process.write_disk_usage - p.write_mb)),
+            process,
+        };
+
+        self.previous.insert(
+            record.process.pid,
+            Previous {
+                start_time: record.process.start_time,
+                elapsed_secs: tick.elapsed_secs,
+                cpu_time_ms,
+                read_mb: record.process.read_disk_usage,
+                write_mb: record.process.write_disk_usage,
+            },
+        );
+        record
+    }
+}
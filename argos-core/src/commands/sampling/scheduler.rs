@@ -0,0 +1,101 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::stats::{mean, percentile};
+
+/// Programa iteraciones en instantes absolutos (`inicio + n × intervalo`): el
+/// tiempo que tarda cada lectura no se acumula. Si una iteración llega tarde
+/// más de un intervalo completo, los instantes que ya pasaron se cuentan como
+/// perdidos y se sigue por el siguiente.
+pub struct TickScheduler {
+    start: Instant,
+    interval: Duration,
+    next: u32,
+    missed: u32,
+    last: Option<Instant>,
+    jitter_ms: Vec<f64>,
+    intervals_ms: Vec<f64>,
+}
+
+/// Precisión conseguida por el planificador.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleReport {
+    pub interval_ms: f64,          // pedido
+    pub mean_interval_ms: f64,     // real, entre iteraciones consecutivas
+    pub ticks: usize,
+    pub missed_ticks: u32,
+    pub jitter_mean_ms: f64,       // retraso respecto al instante previsto
+    pub jitter_p95_ms: f64,
+    pub jitter_max_ms: f64,
+}
+
+impl TickScheduler {
+    /// La primera iteración se programa un intervalo después de `start`, para
+    /// que la primera medida de CPU cubra un intervalo completo.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            start: Instant::now(),
+            interval,
+            next: 1,
+            missed: 0,
+            last: None,
+            jitter_ms: Vec::new(),
+            intervals_ms: Vec::new(),
+        }
+    }
+
+    fn deadline(&self, tick: u32) -> Instant {
+        self.start + self.interval * tick
+    }
+
+    /// Espera hasta el siguiente instante programado.
+    pub fn wait(&mut self) {
+        self.wait_unless(Duration::MAX, || false);
+    }
+
+    /// Como [`wait`](Self::wait), pero comprobando `done` cada `poll` mientras
+    /// espera. Si se cumple, vuelve enseguida con `true` sin consumir la iteración.
+    pub fn wait_unless(&mut self, poll: Duration, mut done: impl FnMut() -> bool) -> bool {
+        let now = Instant::now();
+        // Instantes que ya pasaron de largo: se pierden
+        while self.deadline(self.next) + self.interval <= now {
+            self.next += 1;
+            self.missed += 1;
+        }
+
+        let deadline = self.deadline(self.next);
+        while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+            if remaining.is_zero() {
+                break;
+            }
+            thread::sleep(remaining.min(poll));
+            if done() {
+                return true;
+            }
+        }
+
+        let woke = Instant::now();
+        self.jitter_ms.push(woke.saturating_duration_since(deadline).as_secs_f64() * 1000.0);
+        if let Some(last) = self.last {
+            self.intervals_ms.push((woke - last).as_secs_f64() * 1000.0);
+        }
+        self.last = Some(woke);
+        self.next += 1;
+        false
+    }
+
+    pub fn report(&self) -> ScheduleReport {
+        let interval_ms = self.interval.as_secs_f64() * 1000.0;
+        ScheduleReport {
+            interval_ms,
+            mean_interval_ms: if self.intervals_ms.is_empty() { interval_ms } else { mean(&self.intervals_ms) },
+            ticks: self.jitter_ms.len(),
+            missed_ticks: self.missed,
+            jitter_mean_ms: mean(&self.jitter_ms),
+            jitter_p95_ms: percentile(&self.jitter_ms, 95.0),
+            jitter_max_ms: self.jitter_ms.iter().cloned().fold(0.0, f64::max),
+        }
+    }
+}
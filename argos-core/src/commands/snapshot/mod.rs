@@ -0,0 +1,168 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sysinfo::System;
+
+use crate::errors::{CoreError, CoreResult};
+use crate::process::{model::ProcessRow, reader::ProcessReader};
+
+/// Versión actual del formato de snapshot. La 1 era la lista de procesos sin
+/// envoltorio que escribía `list -f json`.
+pub const SCHEMA_VERSION: u32 = 2;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Equipo y momento en que se tomó el snapshot. En snapshots migrados desde la
+/// versión 1 estos datos se desconocen.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotHost {
+    pub hostname: Option<String>,
+    pub kernel: Option<String>,
+    pub os: Option<String>,
+    pub boot_id: Option<String>,
+    pub argos_version: Option<String>,
+}
+
+/// Estado global del sistema al tomar el snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemSummary {
+    pub cpu_count: usize,
+    pub cpu_usage: f32,
+    pub total_memory_mb: f64,
+    pub used_memory_mb: f64,
+    pub total_swap_mb: f64,
+    pub used_swap_mb: f64,
+    pub load_average: [f64; 3],
+    pub uptime_secs: u64,
+    pub process_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub schema_version: u32,
+    pub captured_at: Option<DateTime<Utc>>,
+    pub host: SnapshotHost,
+    pub system: Option<SystemSummary>,
+    pub processes: Vec<ProcessRow>,
+}
+
+#[cfg(target_os = "linux")]
+fn boot_id() -> Option<String> {
+    std::fs::read_to_string("/proc/sys/kernel/random/boot_id")
+        .ok()
+        .map(|id| id.trim().to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn boot_id() -> Option<String> {
+    None
+}
+
+fn system_summary(system: &System, process_count: usize) -> SystemSummary {
+    let mb = |bytes: u64| bytes as f64 / 1_048_576.0;
+    let load = System::load_average();
+    SystemSummary {
+        cpu_count: system.cpus().len(),
+        cpu_usage: system.global_cpu_info().cpu_usage(),
+        total_memory_mb: mb(system.total_memory()),
+        used_memory_mb: mb(system.used_memory()),
+        total_swap_mb: mb(system.total_swap()),
+        used_swap_mb: mb(system.used_swap()),
+        load_average: [load.one, load.five, load.fifteen],
+        uptime_secs: System::uptime(),
+        process_count,
+    }
+}
+
+/// Toma un snapshot de todos los procesos (sin hilos) con los datos del equipo.
+pub fn capture_snapshot() -> CoreResult<Snapshot> {
+    let mut reader = ProcessReader::new();
+    let processes = reader.get_processes();
+    if processes.is_empty() {
+        return Err(CoreError::ProcessNotFoundList(vec![]));
+    }
+
+    Ok(Snapshot {
+        schema_version: SCHEMA_VERSION,
+        captured_at: Some(Utc::now()),
+        host: SnapshotHost {
+            hostname: System::host_name(),
+            kernel: System::kernel_version(),
+            os: System::long_os_version(),
+            boot_id: boot_id(),
+            argos_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        },
+        system: Some(system_summary(reader.system(), processes.len())),
+        processes,
+    })
+}
+
+/// Escribe el snapshot en JSON, comprimido con gzip si `gzip` es verdadero.
+pub fn write_snapshot(snapshot: &Snapshot, path: &Path, gzip: bool) -> CoreResult<()> {
+    let file = BufWriter::new(File::create(path)?);
+    if gzip {
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        serde_json::to_writer_pretty(&mut encoder, snapshot)?;
+        encoder.finish()?.flush()?;
+    } else {
+        let mut file = file;
+        serde_json::to_writer_pretty(&mut file, snapshot)?;
+        file.flush()?;
+    }
+    Ok(())
+}
+
+/// Convierte un documento de una versión anterior del formato a la actual,
+/// versión a versión.
+fn migrate(mut document: Value) -> CoreResult<Value> {
+    loop {
+        let version = match &document {
+            // Versión 1: lista de procesos sin envoltorio
+            Value::Array(_) => 1,
+            Value::Object(map) => map
+                .get("schema_version")
+                .and_then(Value::as_u64)
+                .ok_or_else(|| CoreError::ValidationError("El snapshot no indica `schema_version`".into()))?
+                as u32,
+            _ => return Err(CoreError::ValidationError("El archivo no es un snapshot de Argos".into())),
+        };
+
+        document = match version {
+            1 => serde_json::json!({
+                "schema_version": 2,
+                "captured_at": null,
+                "host": {},
+                "system": null,
+                "processes": document,
+            }),
+            SCHEMA_VERSION => return Ok(document),
+            other => {
+                return Err(CoreError::ValidationError(format!(
+                    "Versión de snapshot no soportada: {} (esta versión de Argos lee hasta la {})",
+                    other, SCHEMA_VERSION
+                )))
+            }
+        };
+    }
+}
+
+/// Lee un snapshot, comprimido o no, en cualquier versión del formato.
+pub fn read_snapshot(path: &Path) -> CoreResult<Snapshot> {
+    let mut data = Vec::new();
+    BufReader::new(File::open(path)?).read_to_end(&mut data)?;
+    if data.starts_with(&GZIP_MAGIC) {
+        let mut decoded = Vec::new();
+        GzDecoder::new(data.as_slice()).read_to_end(&mut decoded)?;
+        data = decoded;
+    }
+
+    let document: Value = serde_json::from_slice(&data)?;
+    Ok(serde_json::from_value(migrate(document)?)?)
+}
@@ -0,0 +1,327 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    commands::sampling::scheduler::TickScheduler,
+    errors::{CoreError, CoreResult},
+    process::{filter::matches_pattern, model::ProcessRow, reader::ProcessReader},
+};
+
+/// Columna por la que ordenar. Las numéricas van de mayor a menor y las de
+/// texto (y el PID) de menor a mayor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TopSort {
+    Cpu,
+    Memory,
+    Vmem,
+    Read,
+    Write,
+    Pid,
+    Name,
+    User,
+}
+
+impl TopSort {
+    fn compare(&self, a: &ProcessRow, b: &ProcessRow) -> Ordering {
+        let desc = |x: f64, y: f64| y.partial_cmp(&x).unwrap_or(Ordering::Equal);
+        match self {
+            TopSort::Cpu => desc(a.cpu_usage, b.cpu_usage),
+            TopSort::Memory => desc(a.memory_mb, b.memory_mb),
+            TopSort::Vmem => desc(a.virtual_memory_mb, b.virtual_memory_mb),
+            TopSort::Read => desc(a.read_disk_usage, b.read_disk_usage),
+            TopSort::Write => desc(a.write_disk_usage, b.write_disk_usage),
+            TopSort::Pid => a.pid.cmp(&b.pid),
+            TopSort::Name => a.name.cmp(&b.name),
+            TopSort::User => a.user.cmp(&b.user),
+        }
+    }
+}
+
+impl FromStr for TopSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "cpu" => Ok(TopSort::Cpu),
+            "memory" | "mem" | "ram" => Ok(TopSort::Memory),
+            "vmem" => Ok(TopSort::Vmem),
+            "read" => Ok(TopSort::Read),
+            "write" => Ok(TopSort::Write),
+            "pid" => Ok(TopSort::Pid),
+            "name" => Ok(TopSort::Name),
+            "user" => Ok(TopSort::User),
+            _ => Err(format!("Orden desconocido: {} (use cpu, memory, vmem, read, write, pid, name, user)", s)),
+        }
+    }
+}
+
+impl fmt::Display for TopSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TopSort::Cpu => "cpu",
+            TopSort::Memory => "memory",
+            TopSort::Vmem => "vmem",
+            TopSort::Read => "read",
+            TopSort::Write => "write",
+            TopSort::Pid => "pid",
+            TopSort::Name => "name",
+            TopSort::User => "user",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Campo de un proceso sobre el que filtrar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterField {
+    Cpu,
+    Memory,
+    Vmem,
+    Read,
+    Write,
+    Pid,
+    Ppid,
+    Name,
+    User,
+    State,
+    Cmd,
+}
+
+const FIELDS: [(&str, FilterField); 11] = [
+    ("cpu", FilterField::Cpu),
+    ("mem", FilterField::Memory),
+    ("vmem", FilterField::Vmem),
+    ("read", FilterField::Read),
+    ("write", FilterField::Write),
+    ("pid", FilterField::Pid),
+    ("ppid", FilterField::Ppid),
+    ("name", FilterField::Name),
+    ("user", FilterField::User),
+    ("state", FilterField::State),
+    ("cmd", FilterField::Cmd),
+];
+
+enum FieldValue<'a> {
+    Number(Option<f64>),
+    Text(&'a str),
+}
+
+impl FilterField {
+    fn value<'a>(&self, row: &'a ProcessRow) -> FieldValue<'a> {
+        match self {
+            FilterField::Cpu => FieldValue::Number(Some(row.cpu_usage)),
+            FilterField::Memory => FieldValue::Number(Some(row.memory_mb)),
+            FilterField::Vmem => FieldValue::Number(Some(row.virtual_memory_mb)),
+            FilterField::Read => FieldValue::Number(Some(row.read_disk_usage)),
+            FilterField::Write => FieldValue::Number(Some(row.write_disk_usage)),
+            FilterField::Pid => FieldValue::Number(Some(row.pid as f64)),
+            FilterField::Ppid => FieldValue::Number(row.parent_pid.map(|p| p as f64)),
+            FilterField::Name => FieldValue::Text(&row.name),
+            FilterField::User => FieldValue::Text(&row.user),
+            FilterField::State => FieldValue::Text(&row.state),
+            FilterField::Cmd => FieldValue::Text(&row.cmd),
+        }
+    }
+
+    fn is_text(&self) -> bool {
+        matches!(self, FilterField::Name | FilterField::User | FilterField::State | FilterField::Cmd)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl fmt::Display for FilterOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            FilterOp::Equal => "=",
+            FilterOp::NotEqual => "!=",
+            FilterOp::Greater => ">",
+            FilterOp::GreaterOrEqual => ">=",
+            FilterOp::Less => "<",
+            FilterOp::LessOrEqual => "<=",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+/// Filtro de `--where`, p. ej. `cpu > 5`, `user = root` o `name != kworker*`.
+/// Los campos de texto solo admiten `=` y `!=`, con comodines `*` (en `cmd`
+/// sin comodines se busca como subcadena).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessFilter {
+    pub field: FilterField,
+    pub op: FilterOp,
+    pub value: String,
+}
+
+impl ProcessFilter {
+    pub fn matches(&self, row: &ProcessRow) -> bool {
+        match self.field.value(row) {
+            FieldValue::Number(value) => {
+                let (Some(value), Ok(limit)) = (value, self.value.parse::<f64>()) else { return false };
+                match self.op {
+                    FilterOp::Equal => value == limit,
+                    FilterOp::NotEqual => value != limit,
+                    FilterOp::Greater => value > limit,
+                    FilterOp::GreaterOrEqual => value >= limit,
+                    FilterOp::Less => value < limit,
+                    FilterOp::LessOrEqual => value <= limit,
+                }
+            }
+            FieldValue::Text(text) => {
+                let found = if self.field == FilterField::Cmd && !self.value.contains('*') {
+                    text.contains(self.value.as_str())
+                } else {
+                    matches_pattern(&self.value, text)
+                };
+                found == (self.op == FilterOp::Equal)
+            }
+        }
+    }
+}
+
+impl FromStr for ProcessFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Los operadores de dos caracteres van primero para no partir `>=` en `>`
+        let operators = [
+            (">=", FilterOp::GreaterOrEqual),
+            ("<=", FilterOp::LessOrEqual),
+            ("!=", FilterOp::NotEqual),
+            ("==", FilterOp::Equal),
+            (">", FilterOp::Greater),
+            ("<", FilterOp::Less),
+            ("=", FilterOp::Equal),
+        ];
+        let (field, op, value) = operators
+            .iter()
+            .filter_map(|(symbol, op)| s.find(symbol).map(|i| (i, symbol, *op)))
+            // El operador que aparece antes; a igual posición, el más largo (van primero)
+            .min_by_key(|(i, _, _)| *i)
+            .map(|(i, symbol, op)| (s[..i].trim(), op, s[i + symbol.len()..].trim()))
+            .ok_or_else(|| format!("Filtro inválido (use <campo> <op> <valor>): {}", s))?;
+
+        let field = FIELDS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(field))
+            .map(|(_, f)| *f)
+            .ok_or_else(|| {
+                let names: Vec<&str> = FIELDS.iter().map(|(name, _)| *name).collect();
+                format!("Campo desconocido: {} (use {})", field, names.join(", "))
+            })?;
+        if value.is_empty() {
+            return Err(format!("Falta el valor en el filtro: {}", s));
+        }
+        if field.is_text() {
+            if !matches!(op, FilterOp::Equal | FilterOp::NotEqual) {
+                return Err(format!("El campo {} solo admite = y !=: {}", field, s));
+            }
+        } else if value.trim_end_matches('%').parse::<f64>().is_err() {
+            return Err(format!("Valor inválido en el filtro '{}': {}", s, value));
+        }
+
+        Ok(ProcessFilter { field, op, value: value.trim_end_matches('%').to_string() })
+    }
+}
+
+impl fmt::Display for FilterField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = FIELDS.iter().find(|(_, field)| field == self).map_or("?", |(name, _)| name);
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for ProcessFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.field, self.op, self.value)
+    }
+}
+
+/// Qué mostrar en cada iteración de `top`.
+#[derive(Debug, Clone)]
+pub struct TopOptions {
+    pub limit: usize,
+    pub sort_by: TopSort,
+    pub filters: Vec<ProcessFilter>, // deben cumplirse todos
+    pub interval: Duration,
+    pub iterations: Option<usize>, // sin límite hasta que se interrumpa
+}
+
+/// Una iteración: los primeros procesos según el orden y cuántos había.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopFrame {
+    pub iteration: usize, // desde 1
+    pub captured_at: DateTime<Utc>,
+    pub sort_by: TopSort,
+    pub filters: Vec<String>,
+    pub total_processes: usize,
+    pub matched: usize, // que cumplen los filtros
+    pub total_cpu: f64,
+    pub total_memory_mb: f64,
+    pub processes: Vec<ProcessRow>,
+}
+
+/// Muestra los procesos que más consumen cada `interval`, como `top`. La CPU
+/// de cada iteración se mide sobre el intervalo anterior, así que la primera
+/// llega tras un intervalo completo. Termina tras `iterations` o cuando
+/// `callback` devuelve `false`.
+pub fn monitor_top<F>(options: &TopOptions, mut callback: F) -> CoreResult<()>
+where
+    F: FnMut(&TopFrame) -> bool,
+{
+    if options.interval.is_zero() {
+        return Err(CoreError::ValidationError("El intervalo debe ser de al menos 1 ms".into()));
+    }
+    if options.iterations == Some(0) {
+        return Err(CoreError::ValidationError("El número de iteraciones debe ser mayor que 0".into()));
+    }
+
+    let mut reader = ProcessReader::new();
+    let mut scheduler = TickScheduler::new(options.interval);
+    let filters: Vec<String> = options.filters.iter().map(|f| f.to_string()).collect();
+
+    for iteration in 1.. {
+        scheduler.wait();
+        reader.refresh_processes();
+        let captured_at = Utc::now();
+        let rows = reader.current_processes();
+
+        let mut matched: Vec<ProcessRow> =
+            rows.iter().filter(|r| options.filters.iter().all(|f| f.matches(r))).cloned().collect();
+        matched.sort_by(|a, b| options.sort_by.compare(a, b).then(a.pid.cmp(&b.pid)));
+        let count = matched.len();
+        matched.truncate(options.limit);
+
+        let go_on = callback(&TopFrame {
+            iteration,
+            captured_at,
+            sort_by: options.sort_by,
+            filters: filters.clone(),
+            total_processes: rows.len(),
+            matched: count,
+            total_cpu: rows.iter().map(|r| r.cpu_usage).sum(),
+            total_memory_mb: rows.iter().map(|r| r.memory_mb).sum(),
+            processes: matched,
+        });
+
+        if !go_on || options.iterations.is_some_and(|n| iteration >= n) {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -1,44 +1,334 @@
+use std::fmt;
 use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::anomaly::{Anomaly, AnomalyMetric};
+use crate::commands::kill::kill_process;
+use crate::commands::renice::renice_process;
+use crate::commands::watchdog::exec::{run_exec, ExecResult, DEFAULT_EXEC_TIMEOUT};
+use crate::notify::{NotifierRegistry, NotifyResult};
+
+/// Métricas de un proceso en una iteración del watchdog. Las que dependen de la
+/// plataforma o del historial (tasas) quedan en `None` si no se pueden calcular,
+/// y las condiciones sobre ellas no se cumplen.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessMetrics {
+    pub cpu_usage: f32,
+    pub memory_bytes: u64,
+    pub virtual_memory_bytes: u64,
+    pub disk_read_per_sec: Option<f64>,     // bytes/s
+    pub disk_write_per_sec: Option<f64>,    // bytes/s
+    pub open_fds: Option<u64>,
+    pub threads: Option<u64>,
+    pub state: String,                      // `ProcessStatus` de sysinfo (`Zombie`, `UninterruptibleDiskSleep`...)
+    pub runtime_secs: u64,
+    pub memory_growth_per_min: Option<f64>, // bytes/min
+    pub spawn_rate_per_min: Option<f64>,    // hijos nuevos por minuto
+    pub anomalies: Vec<Anomaly>,            // anomalías en curso respecto a la línea base del proceso
+}
 
 #[derive(Debug, Clone)]
 pub enum Condition {
-    CpuAbove(f32),   // Ej: CPU > 80%
-    MemAbove(u64),   // Ej: Memoria > 1 GB
-    ProcessExit,     // Ej: Proceso terminó
+    CpuAbove(f32),          // Ej: CPU > 80%
+    MemAbove(u64),          // Ej: Memoria > 1 GB
+    VirtMemAbove(u64),      // Ej: memoria virtual > 8 GB
+    DiskReadAbove(u64),     // bytes leídos por segundo
+    DiskWriteAbove(u64),    // bytes escritos por segundo
+    OpenFdsAbove(u64),
+    ThreadsAbove(u64),
+    StateIs(String),        // Ej: state == zombie
+    RuntimeAbove(u64),      // segundos desde que arrancó el proceso
+    MemGrowthAbove(u64),    // bytes por minuto
+    SpawnRateAbove(f64),    // hijos nuevos por minuto (fork storms)
+    Anomalous(AnomalyMetric), // la métrica se sale de lo habitual para el proceso
+    ProcessExit,            // Ej: Proceso terminó
 }
 
 impl Condition {
-    pub fn is_triggered(&self, cpu: f32, mem: u64) -> bool {
+    pub fn is_triggered(&self, m: &ProcessMetrics) -> bool {
+        let above = |value: Option<f64>, limit: f64| value.is_some_and(|v| v > limit);
         match self {
-            Condition::CpuAbove(limit) => cpu > *limit,
-            Condition::MemAbove(limit) => mem > *limit,
-            Condition::ProcessExit => false, // se maneja aparte
+            Condition::CpuAbove(limit) => m.cpu_usage > *limit,
+            Condition::MemAbove(limit) => m.memory_bytes > *limit,
+            Condition::VirtMemAbove(limit) => m.virtual_memory_bytes > *limit,
+            Condition::DiskReadAbove(limit) => above(m.disk_read_per_sec, *limit as f64),
+            Condition::DiskWriteAbove(limit) => above(m.disk_write_per_sec, *limit as f64),
+            Condition::OpenFdsAbove(limit) => m.open_fds.is_some_and(|v| v > *limit),
+            Condition::ThreadsAbove(limit) => m.threads.is_some_and(|v| v > *limit),
+            Condition::StateIs(state) => canonical_state(&m.state) == canonical_state(state),
+            Condition::RuntimeAbove(limit) => m.runtime_secs > *limit,
+            Condition::MemGrowthAbove(limit) => above(m.memory_growth_per_min, *limit as f64),
+            Condition::SpawnRateAbove(limit) => above(m.spawn_rate_per_min, *limit),
+            Condition::Anomalous(metric) => m.anomalies.iter().any(|a| a.metric == *metric),
+            Condition::ProcessExit => false, // lo dispara el watchdog cuando el proceso desaparece
+        }
+    }
+
+    /// Métrica que evalúa la condición y su valor en `m` (en bytes, bytes/s,
+    /// segundos...), para que las acciones sepan qué disparó la regla.
+    pub fn observed(&self, m: &ProcessMetrics) -> (String, Option<String>) {
+        let rate = |value: Option<f64>| value.map(|v| format!("{:.0}", v));
+        let (metric, value) = match self {
+            Condition::CpuAbove(_) => ("cpu", Some(format!("{:.2}", m.cpu_usage))),
+            Condition::MemAbove(_) => ("mem", Some(m.memory_bytes.to_string())),
+            Condition::VirtMemAbove(_) => ("vmem", Some(m.virtual_memory_bytes.to_string())),
+            Condition::DiskReadAbove(_) => ("read", rate(m.disk_read_per_sec)),
+            Condition::DiskWriteAbove(_) => ("write", rate(m.disk_write_per_sec)),
+            Condition::OpenFdsAbove(_) => ("fds", m.open_fds.map(|v| v.to_string())),
+            Condition::ThreadsAbove(_) => ("threads", m.threads.map(|v| v.to_string())),
+            Condition::StateIs(_) => ("state", Some(m.state.clone())),
+            Condition::RuntimeAbove(_) => ("runtime", Some(m.runtime_secs.to_string())),
+            Condition::MemGrowthAbove(_) => ("mem_growth", rate(m.memory_growth_per_min)),
+            Condition::SpawnRateAbove(_) => ("spawn_rate", m.spawn_rate_per_min.map(|v| format!("{:.2}", v))),
+            Condition::Anomalous(metric) => {
+                return (metric.to_string(), metric.value(m).map(|v| format!("{:.2}", v)));
+            }
+            Condition::ProcessExit => ("exit", None),
+        };
+        (metric.to_string(), value)
+    }
+
+    /// Indica si la condición necesita conocer los hijos de cada proceso.
+    pub fn needs_children(&self) -> bool {
+        matches!(self, Condition::SpawnRateAbove(_))
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::CpuAbove(limit) => write!(f, "cpu > {}", limit),
+            Condition::MemAbove(limit) => write!(f, "mem > {}", limit),
+            Condition::VirtMemAbove(limit) => write!(f, "vmem > {}", limit),
+            Condition::DiskReadAbove(limit) => write!(f, "read > {}", limit),
+            Condition::DiskWriteAbove(limit) => write!(f, "write > {}", limit),
+            Condition::OpenFdsAbove(limit) => write!(f, "fds > {}", limit),
+            Condition::ThreadsAbove(limit) => write!(f, "threads > {}", limit),
+            Condition::StateIs(state) => write!(f, "state == {}", state),
+            Condition::RuntimeAbove(limit) => write!(f, "runtime > {}s", limit),
+            Condition::MemGrowthAbove(limit) => write!(f, "mem_growth > {}", limit),
+            Condition::SpawnRateAbove(limit) => write!(f, "spawn_rate > {}", limit),
+            Condition::Anomalous(metric) => write!(f, "anomalous({})", metric),
+            Condition::ProcessExit => write!(f, "exit"),
+        }
+    }
+}
+
+/// Estado normalizado para comparar: minúsculas, sin separadores y con los
+/// nombres habituales (`running`, `sleeping`, `D`...) traducidos a los de
+/// `ProcessStatus` de sysinfo.
+fn canonical_state(state: &str) -> String {
+    let state: String = state.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+    match state.as_str() {
+        "r" | "running" => "run".into(),
+        "s" | "sleeping" => "sleep".into(),
+        "d" | "uninterruptible" | "disksleep" => "uninterruptibledisksleep".into(),
+        "t" | "stopped" => "stop".into(),
+        "z" => "zombie".into(),
+        "x" => "dead".into(),
+        "i" => "idle".into(),
+        "traced" => "tracing".into(),
+        _ => state,
+    }
+}
+
+/// Interpreta tamaños como `1073741824`, `512K`, `256M` o `1G` (en bytes).
+pub fn parse_bytes(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let (number, multiplier) = match value.chars().last()?.to_ascii_uppercase() {
+        'K' => (&value[..value.len() - 1], 1024),
+        'M' => (&value[..value.len() - 1], 1024 * 1024),
+        'G' => (&value[..value.len() - 1], 1024 * 1024 * 1024),
+        _ => (value, 1),
+    };
+    number.trim().parse::<f64>().ok().map(|n| (n * multiplier as f64) as u64)
+}
+
+/// Interpreta duraciones como `90`, `90s`, `30m`, `2h` o `1d` (en segundos).
+pub fn parse_duration_secs(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let (number, multiplier) = match value.chars().last()?.to_ascii_lowercase() {
+        's' => (&value[..value.len() - 1], 1),
+        'm' => (&value[..value.len() - 1], 60),
+        'h' => (&value[..value.len() - 1], 3600),
+        'd' => (&value[..value.len() - 1], 86400),
+        _ => (value, 1),
+    };
+    number.trim().parse::<f64>().ok().map(|n| (n * multiplier as f64) as u64)
+}
+
+impl FromStr for Condition {
+    type Err = String;
+
+    /// Sintaxis: `cpu > 80`, `mem > 512M`, `vmem > 8G`, `read > 10M` (por segundo),
+    /// `write > 10M`, `fds > 1000`, `threads > 200`, `state == zombie`,
+    /// `runtime > 2h`, `mem_growth > 50M` (por minuto), `spawn_rate > 30` (por minuto),
+    /// `anomalous(cpu)` (también `mem`, `read`, `write`, `fds`, `threads`), `exit`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("exit") {
+            return Ok(Condition::ProcessExit);
+        }
+
+        let lower = s.to_lowercase();
+        if let Some(metric) = lower.strip_prefix("anomalous").map(str::trim_start) {
+            return metric
+                .strip_prefix('(')
+                .and_then(|m| m.strip_suffix(')'))
+                .ok_or_else(|| format!("Condición inválida: {} (use anomalous(cpu))", s))?
+                .parse()
+                .map(Condition::Anomalous);
+        }
+
+        if let Some((metric, value)) = s.split_once("==").or_else(|| s.split_once('=')) {
+            let value = value.trim();
+            return match metric.trim().to_lowercase().as_str() {
+                "state" | "status" if !value.is_empty() => Ok(Condition::StateIs(value.to_string())),
+                "state" | "status" => Err(format!("Estado vacío: {}", s)),
+                other => Err(format!("Métrica desconocida: {}", other)),
+            };
+        }
+
+        let (metric, value) = s
+            .split_once('>')
+            .ok_or_else(|| format!("Condición inválida: {}", s))?;
+        let value = value.trim();
+        let bytes = |kind: &str| parse_bytes(value).ok_or_else(|| format!("Umbral de {} inválido: {}", kind, value));
+        let count = |kind: &str| value.parse::<u64>().map_err(|_| format!("Umbral de {} inválido: {}", kind, value));
+
+        match metric.trim().to_lowercase().as_str() {
+            "cpu" => value
+                .parse()
+                .map(Condition::CpuAbove)
+                .map_err(|_| format!("Umbral de CPU inválido: {}", value)),
+            "mem" | "memory" => bytes("memoria").map(Condition::MemAbove),
+            "vmem" | "virtual_memory" => bytes("memoria virtual").map(Condition::VirtMemAbove),
+            "read" | "disk_read" => bytes("lectura").map(Condition::DiskReadAbove),
+            "write" | "disk_write" => bytes("escritura").map(Condition::DiskWriteAbove),
+            "fds" | "open_fds" => count("descriptores").map(Condition::OpenFdsAbove),
+            "threads" => count("hilos").map(Condition::ThreadsAbove),
+            "runtime" => parse_duration_secs(value)
+                .map(Condition::RuntimeAbove)
+                .ok_or_else(|| format!("Duración inválida: {}", value)),
+            "mem_growth" => bytes("crecimiento de memoria").map(Condition::MemGrowthAbove),
+            "spawn_rate" => value
+                .parse()
+                .map(Condition::SpawnRateAbove)
+                .map_err(|_| format!("Umbral de procesos hijos inválido: {}", value)),
+            other => Err(format!("Métrica desconocida: {}", other)),
         }
     }
 }
 
+/// Contexto de una alerta que se entrega a las acciones al dispararse una regla.
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertContext {
+    pub rule: String,
+    pub pid: u32,
+    pub name: String,
+    pub condition: String,
+    pub metric: String,        // métrica de la condición que se cumplió (`fds`, `state`...)
+    pub value: Option<String>, // su valor al dispararse, si se conoce
+    pub cpu_usage: f32,
+    pub memory_bytes: u64,
+    pub triggered_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone)]
 pub enum Action {
     Log(String),
     Kill,
+    Renice(i32),    // nuevo valor de nice
     Export(String),
+    Exec { command: String, timeout: Duration },
+    Notify(String), // nombre del canal configurado
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Log(_) => write!(f, "log"),
+            Action::Kill => write!(f, "kill"),
+            Action::Renice(nice) => write!(f, "renice:{}", nice),
+            Action::Export(path) => write!(f, "export:{}", path),
+            Action::Exec { command, .. } => write!(f, "exec:{}", command),
+            Action::Notify(channel) => write!(f, "notify:{}", channel),
+        }
+    }
+}
+
+/// Resultado de ejecutar una acción.
+#[derive(Debug, Clone)]
+pub enum ActionOutcome {
+    Done,
+    Failed(String), // kill o renice rechazados (sin permisos, el proceso ya no existe...)
+    Exec(ExecResult),
+    Notify(NotifyResult),
+}
+
+impl ActionOutcome {
+    /// Resumen legible del resultado, si la acción produce alguno.
+    pub fn summary(&self) -> Option<String> {
+        match self {
+            ActionOutcome::Done => None,
+            ActionOutcome::Failed(e) => Some(format!("fallida: {}", e)),
+            ActionOutcome::Exec(ExecResult { error: Some(e), .. }) => Some(format!("fallida: {}", e)),
+            ActionOutcome::Exec(result) if result.timed_out => Some("timeout".to_string()),
+            ActionOutcome::Exec(result) => Some(result.exit_code.map_or("signal".to_string(), |c| format!("exit {}", c))),
+            ActionOutcome::Notify(result) => Some(match &result.error {
+                None => "entregada".to_string(),
+                Some(e) => format!("fallida: {}", e),
+            }),
+        }
+    }
 }
 
 impl Action {
-    pub fn execute(&self, process: &sysinfo::Process) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn execute(
+        &self,
+        ctx: &AlertContext,
+        notifiers: &NotifierRegistry,
+    ) -> Result<ActionOutcome, Box<dyn std::error::Error>> {
         match self {
             Action::Log(msg) => {
                 println!("[WATCHDOG] {}", msg);
             }
+            // Sin permisos (bajar la prioridad exige CAP_SYS_NICE) o con el
+            // proceso ya terminado, el fallo queda en el incidente y el tick sigue
             Action::Kill => {
-                process.kill();
+                if let Err(e) = kill_process(ctx.pid) {
+                    return Ok(ActionOutcome::Failed(e.to_string()));
+                }
+            }
+            Action::Renice(nice) => {
+                if let Err(e) = renice_process(ctx.pid, *nice) {
+                    return Ok(ActionOutcome::Failed(e.to_string()));
+                }
             }
             Action::Export(path) => {
                 // Aquí podrías reutilizar tu lógica de exportación
                 println!("Exportando métricas a {}", path);
             }
+            Action::Exec { command, timeout } => {
+                // Como con los canales, un hook que no arranca queda en el reporte
+                return Ok(ActionOutcome::Exec(run_exec(command, *timeout, ctx)));
+            }
+            Action::Notify(channel) => {
+                // Un canal caído no detiene el watchdog: el fallo queda en el reporte
+                let error = notifiers.notify(channel, ctx).err().map(|e| e.to_string());
+                return Ok(ActionOutcome::Notify(NotifyResult {
+                    rule: ctx.rule.clone(),
+                    pid: ctx.pid,
+                    channel: channel.clone(),
+                    delivered: error.is_none(),
+                    error,
+                }));
+            }
         }
-        Ok(())
+        Ok(ActionOutcome::Done)
     }
 }
 
@@ -46,6 +336,23 @@ impl FromStr for Action {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // exec:<comando> y notify:<canal> conservan el argumento tal cual (sin pasar a minúsculas)
+        if let Some((kind, arg)) = s.split_once(':') {
+            let arg = arg.trim();
+            if kind.eq_ignore_ascii_case("exec") && !arg.is_empty() {
+                return Ok(Action::Exec { command: arg.to_string(), timeout: DEFAULT_EXEC_TIMEOUT });
+            }
+            if kind.eq_ignore_ascii_case("notify") && !arg.is_empty() {
+                return Ok(Action::Notify(arg.to_string()));
+            }
+            if kind.eq_ignore_ascii_case("renice") {
+                return match arg.parse::<i32>() {
+                    Ok(nice) if (-20..=19).contains(&nice) => Ok(Action::Renice(nice)),
+                    _ => Err(format!("Valor de nice inválido (de -20 a 19): {}", arg)),
+                };
+            }
+        }
+
         match s.to_lowercase().as_str() {
             "kill" => Ok(Action::Kill),
             "log" => Ok(Action::Log("Mensaje".into())), // necesitas un valor por defecto
@@ -54,3 +361,117 @@ impl FromStr for Action {
         }
     }
 }
+
+/// Procesos a los que se aplica una regla.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    Pid(u32),
+    Name(String), // admite comodines `*`
+}
+
+impl Target {
+    pub fn matches(&self, pid: u32, name: &str) -> bool {
+        match self {
+            Target::Pid(target) => *target == pid,
+            Target::Name(pattern) => crate::process::filter::matches_pattern(pattern, name),
+        }
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::Pid(pid) => write!(f, "pid {}", pid),
+            Target::Name(pattern) => write!(f, "'{}'", pattern),
+        }
+    }
+}
+
+/// Paso de escalado: sus acciones se ejecutan una vez cuando el incidente
+/// lleva abierto al menos `after`.
+#[derive(Debug, Clone)]
+pub struct EscalationStep {
+    pub after: Duration,
+    pub actions: Vec<Action>,
+}
+
+/// Regla del watchdog: si se cumple alguna condición se ejecutan sus acciones.
+/// Sin `target` la regla se aplica al PID indicado al arrancar el watchdog.
+///
+/// `actions` se ejecutan en cada iteración mientras la condición se cumple (los
+/// `notify`, solo al abrirse el incidente); los pasos de `escalation`
+/// (ordenados por `after`) una sola vez por incidente, y vuelven a empezar
+/// cuando la condición deja de cumplirse.
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub name: String,
+    pub target: Option<Target>,
+    pub conditions: Vec<Condition>,
+    pub actions: Vec<Action>,
+    pub escalation: Vec<EscalationStep>,
+}
+
+impl Rule {
+    /// Acciones a ejecutar en esta iteración de un incidente abierto hace `elapsed`.
+    /// `level` es el número de pasos de escalado ya ejecutados y se actualiza.
+    /// Los `notify` de `actions` solo avisan en la iteración que abre el
+    /// incidente (`opened`): repetirlos en cada una inundaría el canal, y los
+    /// recordatorios se configuran con el escalado.
+    pub fn actions_due(&self, elapsed: Duration, level: &mut usize, opened: bool) -> Vec<&Action> {
+        let mut due: Vec<&Action> =
+            self.actions.iter().filter(|a| opened || !matches!(a, Action::Notify(_))).collect();
+        while let Some(step) = self.escalation.get(*level) {
+            if elapsed < step.after {
+                break;
+            }
+            due.extend(&step.actions);
+            *level += 1;
+        }
+        due
+    }
+
+    /// Primera condición que se cumple con estas métricas. La usan tanto el
+    /// watchdog en vivo como el backtest, para que ambos evalúen igual.
+    pub fn triggered_condition(&self, metrics: &ProcessMetrics) -> Option<&Condition> {
+        self.conditions.iter().find(|c| c.is_triggered(metrics))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notify::test_alert;
+
+    #[test]
+    fn plain_notify_fires_once_per_incident() {
+        let rule = Rule {
+            name: "runaway".into(),
+            target: None,
+            conditions: vec![],
+            actions: vec![Action::Notify("oncall".into()), Action::Log("cpu".into())],
+            escalation: vec![EscalationStep {
+                after: Duration::from_secs(60),
+                actions: vec![Action::Notify("oncall".into())],
+            }],
+        };
+        let mut level = 0;
+        let names = |due: Vec<&Action>| due.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(names(rule.actions_due(Duration::ZERO, &mut level, true)), ["notify:oncall", "log"]);
+        assert_eq!(names(rule.actions_due(Duration::from_secs(5), &mut level, false)), ["log"]);
+        assert_eq!(names(rule.actions_due(Duration::from_secs(60), &mut level, false)), ["log", "notify:oncall"]);
+        assert_eq!(names(rule.actions_due(Duration::from_secs(65), &mut level, false)), ["log"]);
+    }
+
+    #[test]
+    fn kill_and_renice_failures_are_outcomes_not_errors() {
+        // Un PID que no existe: la señal y setpriority fallan con ESRCH
+        let ctx = AlertContext { pid: u32::MAX - 1, ..test_alert() };
+        let notifiers = NotifierRegistry::default();
+        for action in [Action::Kill, Action::Renice(10)] {
+            let outcome = action.execute(&ctx, &notifiers).unwrap();
+            assert!(matches!(outcome, ActionOutcome::Failed(_)), "{action}: {outcome:?}");
+            assert!(outcome.summary().unwrap().starts_with("fallida: "));
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::commands::snapshot::read_snapshot;
+use crate::commands::types::{Action, ProcessMetrics, Rule};
+use crate::db::manager::connect;
+use crate::db::sample::get_samples_with_sessions;
+use crate::errors::{CoreError, CoreResult};
+use crate::process::filter::drop_thread_rows;
+
+use super::metrics::{MetricsTracker, Observation};
+
+/// Medición histórica de un proceso, venga de la base de datos o de un snapshot.
+/// Las métricas que la fuente no registra quedan en su valor por defecto (`None`).
+#[derive(Debug, Clone)]
+pub struct HistoricalSample {
+    pub timestamp: DateTime<Utc>,
+    pub pid: u32,
+    pub name: String,
+    pub parent_pid: Option<u32>,
+    pub metrics: ProcessMetrics,
+    pub disk_totals: Option<(u64, u64)>,
+}
+
+/// Momento en que una regla se habría disparado y acciones que habría ejecutado.
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestFiring {
+    pub timestamp: DateTime<Utc>,
+    pub rule: String,
+    pub pid: u32,
+    pub name: String,
+    pub condition: String,
+    pub cpu_usage: f32,
+    pub memory_bytes: u64,
+    pub new_incident: bool, // primer disparo desde que la condición empezó a cumplirse
+    pub actions: String,
+}
+
+/// Resumen por regla del backtest.
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestRuleSummary {
+    pub rule: String,
+    pub firings: usize,
+    pub incidents: usize,
+    pub processes: usize,
+    pub first_fired: Option<DateTime<Utc>>,
+    pub last_fired: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestReport {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub samples: usize,
+    pub rules: Vec<BacktestRuleSummary>,
+    pub firings: Vec<BacktestFiring>,
+}
+
+/// Reproduce las reglas sobre muestras históricas sin ejecutar ninguna acción.
+///
+/// Las muestras se agrupan por instante y cada instante equivale a una iteración
+/// del watchdog: una regla se dispara en cada muestra en la que se cumple alguna
+/// condición. Tras un `kill` simulado se ignoran las muestras posteriores de ese PID.
+pub fn backtest(rules: &[Rule], mut samples: Vec<HistoricalSample>) -> BacktestReport {
+    samples.sort_by_key(|s| (s.timestamp, s.pid));
+
+    let mut firings = Vec::new();
+    let mut tracker = MetricsTracker::new();
+    // Incidentes abiertos: instante de inicio y pasos de escalado ejecutados
+    let mut active: HashMap<(&str, u32), (DateTime<Utc>, usize)> = HashMap::new();
+    let mut killed: HashSet<u32> = HashSet::new();
+    let mut ticks: BTreeMap<DateTime<Utc>, Vec<&HistoricalSample>> = BTreeMap::new();
+    for sample in &samples {
+        ticks.entry(sample.timestamp).or_default().push(sample);
+    }
+
+    for (timestamp, tick) in &ticks {
+        let mut still_active = HashMap::new();
+        let mut killed_now = Vec::new();
+
+        // Los hijos solo se conocen si la fuente registra el padre (snapshots completos)
+        let with_parents = tick.iter().any(|s| s.parent_pid.is_some());
+        let mut children: HashMap<u32, HashSet<u32>> = HashMap::new();
+        for sample in tick {
+            if let Some(parent) = sample.parent_pid {
+                children.entry(parent).or_default().insert(sample.pid);
+            }
+        }
+
+        let mut metrics: HashMap<u32, ProcessMetrics> = HashMap::new();
+        for sample in tick {
+            let watched = rules
+                .iter()
+                .any(|r| r.target.as_ref().is_some_and(|t| t.matches(sample.pid, &sample.name)));
+            if !watched || killed.contains(&sample.pid) {
+                continue;
+            }
+            let observation = Observation {
+                at: *timestamp,
+                metrics: sample.metrics.clone(),
+                disk_totals: sample.disk_totals,
+                children: with_parents.then(|| children.remove(&sample.pid).unwrap_or_default()),
+            };
+            metrics.insert(sample.pid, tracker.observe(sample.pid, observation));
+        }
+
+        for rule in rules {
+            let Some(target) = &rule.target else { continue };
+            for sample in tick {
+                if !target.matches(sample.pid, &sample.name) {
+                    continue;
+                }
+                let Some(sample_metrics) = metrics.get(&sample.pid) else { continue };
+                let Some(condition) = rule.triggered_condition(sample_metrics) else {
+                    continue;
+                };
+
+                let key = (rule.name.as_str(), sample.pid);
+                let new_incident = !active.contains_key(&key);
+                let (since, mut level) = active.get(&key).copied().unwrap_or((*timestamp, 0));
+                let elapsed = (*timestamp - since).to_std().unwrap_or_default();
+                let due = rule.actions_due(elapsed, &mut level, new_incident);
+                still_active.insert(key, (since, level));
+
+                firings.push(BacktestFiring {
+                    timestamp: *timestamp,
+                    rule: rule.name.clone(),
+                    pid: sample.pid,
+                    name: sample.name.clone(),
+                    condition: condition.to_string(),
+                    cpu_usage: sample_metrics.cpu_usage,
+                    memory_bytes: sample_metrics.memory_bytes,
+                    new_incident,
+                    actions: due.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(", "),
+                });
+
+                if due.iter().any(|a| matches!(a, Action::Kill)) {
+                    killed_now.push(sample.pid);
+                }
+            }
+        }
+
+        active = still_active;
+        killed.extend(killed_now);
+    }
+
+    let rules = rules
+        .iter()
+        .map(|rule| {
+            let fired: Vec<&BacktestFiring> = firings.iter().filter(|f| f.rule == rule.name).collect();
+            BacktestRuleSummary {
+                rule: rule.name.clone(),
+                firings: fired.len(),
+                incidents: fired.iter().filter(|f| f.new_incident).count(),
+                processes: fired.iter().map(|f| f.pid).collect::<HashSet<_>>().len(),
+                first_fired: fired.first().map(|f| f.timestamp),
+                last_fired: fired.last().map(|f| f.timestamp),
+            }
+        })
+        .collect();
+
+    BacktestReport {
+        from: samples.first().map(|s| s.timestamp),
+        to: samples.last().map(|s| s.timestamp),
+        samples: samples.len(),
+        rules,
+        firings,
+    }
+}
+
+fn in_range(timestamp: DateTime<Utc>, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> bool {
+    from.is_none_or(|from| timestamp >= from) && to.is_none_or(|to| timestamp <= to)
+}
+
+/// Muestras guardadas en la tabla `samples` dentro del rango indicado. El instante
+/// de cada muestra es el inicio de su sesión más su tiempo relativo; la columna
+/// `memory` se interpreta en bytes.
+pub fn load_db_samples(
+    database_url: Option<&str>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> CoreResult<Vec<HistoricalSample>> {
+    let mut conn = connect(database_url)?;
+    let rows = get_samples_with_sessions(&mut conn)?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(sample, session, name)| {
+            // Las muestras recientes guardan su instante; las antiguas, el desfase desde el inicio
+            let timestamp = match sample.captured_at {
+                Some(at) => at.and_utc(),
+                None => {
+                    let started = session.started_at?.and_utc();
+                    started + chrono::Duration::milliseconds((sample.timestamp as f64 * 1000.0) as i64)
+                }
+            };
+            in_range(timestamp, from, to).then(|| HistoricalSample {
+                timestamp,
+                pid: sample.pid.unwrap_or(session.process_pid) as u32,
+                name: name.unwrap_or_default(),
+                parent_pid: None,
+                metrics: ProcessMetrics {
+                    cpu_usage: sample.cpu_usage,
+                    memory_bytes: sample.memory.max(0) as u64,
+                    ..Default::default()
+                },
+                disk_totals: None,
+            })
+        })
+        .collect())
+}
+
+/// Muestras a partir de snapshots. El instante de cada snapshot es el de su
+/// captura o, si no lo registra (formato antiguo), la fecha de modificación del archivo.
+pub fn load_snapshot_samples(
+    files: &[PathBuf],
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> CoreResult<Vec<HistoricalSample>> {
+    let mut samples = Vec::new();
+    for file in files {
+        let snapshot = read_snapshot(file)?;
+        let timestamp = match snapshot.captured_at {
+            Some(at) => at,
+            None => std::fs::metadata(file).and_then(|m| m.modified()).map_err(CoreError::Io)?.into(),
+        };
+        if !in_range(timestamp, from, to) {
+            continue;
+        }
+
+        // Los hilos de snapshots antiguos contarían como hijos creados
+        let rows = drop_thread_rows(snapshot.processes);
+        let mb = |value: f64| (value * 1_048_576.0) as u64;
+        samples.extend(rows.into_iter().map(|row| HistoricalSample {
+            timestamp,
+            pid: row.pid,
+            parent_pid: row.parent_pid,
+            metrics: ProcessMetrics {
+                cpu_usage: row.cpu_usage as f32,
+                memory_bytes: mb(row.memory_mb),
+                virtual_memory_bytes: mb(row.virtual_memory_mb),
+                state: row.state,
+                runtime_secs: (timestamp.timestamp() as u64).saturating_sub(row.start_time),
+                ..Default::default()
+            },
+            disk_totals: Some((mb(row.read_disk_usage), mb(row.write_disk_usage))),
+            name: row.name,
+        }));
+    }
+    Ok(samples)
+}
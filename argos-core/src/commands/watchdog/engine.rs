@@ -0,0 +1,546 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use diesel::SqliteConnection;
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, System};
+
+use crate::anomaly::AnomalyEvent;
+use crate::commands::types::{Action, ActionOutcome, AlertContext, Condition, ProcessMetrics, Rule, Target};
+use crate::db::alert::{close_alert, insert_alert, update_alert_progress};
+use crate::db::manager::connect;
+use crate::errors::{CoreError, CoreResult};
+use crate::models::NewAlert;
+use crate::notify::NotifierRegistry;
+
+use super::metrics::{MetricsTracker, Observation};
+use super::WatchdogReport;
+
+/// Acción ejecutada durante un incidente, tal como se guarda en la tabla `alerts`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionRecord {
+    pub action: String,
+    pub at: DateTime<Utc>,
+    pub result: Option<String>,
+}
+
+/// Incidente abierto: una regla cuya condición sigue cumpliéndose para un proceso.
+#[derive(Debug, Clone)]
+pub struct Incident {
+    pub alert_id: Option<i32>, // fila en `alerts` si se persisten las alertas
+    pub rule: String,
+    pub pid: u32,
+    pub name: String,
+    pub start_time: u64,
+    pub cmd: String,
+    pub condition: String,
+    pub cpu_usage: f32,
+    pub memory_bytes: u64,
+    pub peak_cpu_usage: f32,
+    pub peak_memory_bytes: u64,
+    pub actions: Vec<ActionRecord>,
+    pub escalation_level: usize, // pasos de escalado ya ejecutados
+    pub since: DateTime<Utc>,
+    pub ticks: u64,
+}
+
+impl Incident {
+    fn persist(&mut self, conn: &mut SqliteConnection) -> CoreResult<()> {
+        let actions = serde_json::to_string(&self.actions)?;
+        match self.alert_id {
+            Some(id) => {
+                update_alert_progress(conn, id, self.peak_cpu_usage, self.peak_memory_bytes as i64, &actions)?;
+            }
+            None => {
+                let id = insert_alert(conn, &NewAlert {
+                    rule: &self.rule,
+                    pid: self.pid as i32,
+                    process_name: &self.name,
+                    process_start_time: Some(self.start_time as i64),
+                    process_cmd: Some(&self.cmd),
+                    condition: &self.condition,
+                    cpu_usage: self.cpu_usage,
+                    memory_bytes: self.memory_bytes as i64,
+                    peak_cpu_usage: self.peak_cpu_usage,
+                    peak_memory_bytes: self.peak_memory_bytes as i64,
+                    actions: &actions,
+                    started_at: self.since.naive_utc(),
+                })?;
+                self.alert_id = Some(id);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Lo último que se vio de un proceso vigilado, para las reglas con `exit`.
+#[derive(Debug, Clone)]
+struct SeenProcess {
+    name: String,
+    start_time: u64,
+    cmd: String,
+    cpu_usage: f32,
+    memory_bytes: u64,
+}
+
+/// Ejecuta las acciones que tocan en un incidente y las anota en él y en el reporte.
+fn run_actions(
+    actions: Vec<&Action>,
+    ctx: &AlertContext,
+    condition: &Condition,
+    incident: &mut Incident,
+    notifiers: &NotifierRegistry,
+    report: &mut WatchdogReport,
+    fired: &mut Vec<(AlertContext, Action)>,
+) -> CoreResult<()> {
+    for action in actions {
+        // Ejecutar acción
+        let outcome = action
+            .execute(ctx, notifiers)
+            .map_err(|e| CoreError::WatchdogError(format!("Action execution failed: {}", e)))?;
+        incident.actions.push(ActionRecord {
+            action: action.to_string(),
+            at: Utc::now(),
+            result: outcome.summary(),
+        });
+        match outcome {
+            ActionOutcome::Exec(result) => report.exec_results.push(result),
+            ActionOutcome::Notify(result) => report.notifications.push(result),
+            ActionOutcome::Done | ActionOutcome::Failed(_) => {}
+        }
+
+        // Guardar en historial
+        report.triggered.push((condition.clone(), action.clone()));
+        fired.push((ctx.clone(), action.clone()));
+    }
+    Ok(())
+}
+
+/// Marca como terminadas en la base de datos las alertas de estos incidentes.
+fn close_incidents<'i>(
+    store: &mut Option<SqliteConnection>,
+    incidents: impl Iterator<Item = &'i Incident>,
+) -> CoreResult<()> {
+    if let Some(conn) = store.as_mut() {
+        let now = Utc::now().naive_utc();
+        for id in incidents.filter_map(|i| i.alert_id) {
+            close_alert(conn, id, now)?;
+        }
+    }
+    Ok(())
+}
+
+/// Motor del watchdog. Conserva el estado entre iteraciones (incidentes abiertos,
+/// historial de disparos y lecturas de CPU), de modo que las reglas pueden
+/// recargarse sin perderlo.
+pub struct Watchdog<'a> {
+    rules: Vec<Rule>,
+    notifiers: &'a NotifierRegistry,
+    system: System,
+    active: HashMap<(String, u32), Incident>,
+    alive_pids: HashSet<u32>,
+    last_seen: HashMap<u32, SeenProcess>,
+    tracker: MetricsTracker,
+    anomalies: Vec<AnomalyEvent>, // solo las de la última iteración
+    store: Option<SqliteConnection>,
+    report: WatchdogReport,
+    start: Instant,
+}
+
+impl<'a> Watchdog<'a> {
+    pub fn new(rules: Vec<Rule>, notifiers: &'a NotifierRegistry) -> CoreResult<Self> {
+        validate_rules(&rules, notifiers)?;
+        let pid = rules
+            .iter()
+            .find_map(|r| match r.target {
+                Some(Target::Pid(pid)) => Some(pid),
+                _ => None,
+            })
+            .unwrap_or(0);
+
+        Ok(Self {
+            rules,
+            notifiers,
+            system: System::new_all(),
+            active: HashMap::new(),
+            alive_pids: HashSet::new(),
+            last_seen: HashMap::new(),
+            tracker: MetricsTracker::new(),
+            anomalies: Vec::new(),
+            store: None,
+            report: WatchdogReport {
+                pid,
+                triggered: Vec::new(),
+                exec_results: Vec::new(),
+                notifications: Vec::new(),
+                anomaly_count: 0,
+                duration: Default::default(),
+            },
+            start: Instant::now(),
+        })
+    }
+
+    /// Persiste cada incidente como una fila de la tabla `alerts` de `database_url`.
+    pub fn persist_alerts(&mut self, database_url: Option<&str>) -> CoreResult<()> {
+        self.store = Some(connect(database_url)?);
+        Ok(())
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Reemplaza el conjunto de reglas. Los incidentes de reglas que siguen
+    /// existiendo (por nombre) se conservan; los de reglas eliminadas se cierran.
+    pub fn reload(&mut self, rules: Vec<Rule>) -> CoreResult<()> {
+        validate_rules(&rules, self.notifiers)?;
+        let names: HashSet<&str> = rules.iter().map(|r| r.name.as_str()).collect();
+        let removed: Vec<(String, u32)> = self
+            .active
+            .keys()
+            .filter(|(rule, _)| !names.contains(rule.as_str()))
+            .cloned()
+            .collect();
+        let closed: Vec<Incident> = removed.iter().filter_map(|key| self.active.remove(key)).collect();
+        self.rules = rules;
+        close_incidents(&mut self.store, closed.iter())
+    }
+
+    pub fn active_incidents(&self) -> impl Iterator<Item = &Incident> {
+        self.active.values()
+    }
+
+    /// Indica si queda algo que vigilar: algún PID objetivo vivo o alguna regla por nombre.
+    pub fn has_live_targets(&self) -> bool {
+        self.rules.iter().any(|rule| match &rule.target {
+            Some(Target::Pid(pid)) => self.alive_pids.contains(pid),
+            Some(Target::Name(_)) => true,
+            None => false,
+        })
+    }
+
+    fn needs_children(&self) -> bool {
+        self.rules.iter().any(|r| r.conditions.iter().any(|c| c.needs_children()))
+    }
+
+    fn watches_exit(&self) -> bool {
+        self.rules.iter().any(|r| r.conditions.iter().any(|c| matches!(c, Condition::ProcessExit)))
+    }
+
+    /// Indica si alguna regla vigila anomalías; solo entonces se registran como eventos.
+    fn needs_anomalies(&self) -> bool {
+        self.rules.iter().any(|r| r.conditions.iter().any(|c| matches!(c, Condition::Anomalous(_))))
+    }
+
+    /// Anomalías que empezaron en la última iteración. Cada `tick` las
+    /// reemplaza, así que un daemon no las acumula.
+    pub fn anomalies(&self) -> &[AnomalyEvent] {
+        &self.anomalies
+    }
+
+    fn refresh(&mut self) {
+        let by_name = self.rules.iter().any(|r| matches!(r.target, Some(Target::Name(_))));
+        self.alive_pids.clear();
+
+        // Para conocer los hijos de un proceso hace falta la lista completa
+        // (sin los hilos, que sysinfo lista como procesos en Linux)
+        if by_name || self.needs_children() {
+            self.system.refresh_processes();
+            self.alive_pids.extend(
+                self.system
+                    .processes()
+                    .iter()
+                    .filter(|(_, process)| process.thread_kind().is_none())
+                    .map(|(pid, _)| pid.as_u32()),
+            );
+        } else {
+            let pids: HashSet<u32> = self
+                .rules
+                .iter()
+                .filter_map(|r| match r.target {
+                    Some(Target::Pid(pid)) => Some(pid),
+                    _ => None,
+                })
+                .collect();
+            for pid in pids {
+                // refresh_process devuelve false si el proceso ya no existe (sysinfo no lo elimina de la lista)
+                if self.system.refresh_process(Pid::from_u32(pid)) {
+                    self.alive_pids.insert(pid);
+                }
+            }
+        }
+    }
+
+    /// Ejecuta una iteración: refresca los procesos, evalúa las reglas y ejecuta
+    /// las acciones de las que se disparan. Devuelve lo disparado en esta iteración.
+    pub fn tick(&mut self) -> CoreResult<Vec<(AlertContext, Action)>> {
+        self.refresh();
+        let now = Utc::now();
+        let mut fired = Vec::new();
+        self.anomalies.clear();
+        let mut still_active: HashSet<(String, u32)> = HashSet::new();
+
+        // Métricas de cada proceso vigilado, una sola vez por iteración
+        let mut children: HashMap<u32, HashSet<u32>> = HashMap::new();
+        let needs_children = self.needs_children();
+        let needs_anomalies = self.needs_anomalies();
+        let watches_exit = self.watches_exit();
+        if needs_children {
+            for (pid, process) in self.system.processes() {
+                if process.thread_kind().is_some() {
+                    continue;
+                }
+                if let Some(parent) = process.parent() {
+                    children.entry(parent.as_u32()).or_default().insert(pid.as_u32());
+                }
+            }
+        }
+        let mut metrics: HashMap<u32, ProcessMetrics> = HashMap::new();
+        let mut seen: HashMap<u32, SeenProcess> = HashMap::new();
+        for (pid, process) in self.system.processes() {
+            let pid = pid.as_u32();
+            let watched = self
+                .rules
+                .iter()
+                .any(|r| r.target.as_ref().is_some_and(|t| t.matches(pid, process.name())));
+            // alive_pids no incluye los hilos: cada proceso abre un solo incidente
+            if !self.alive_pids.contains(&pid) || !watched {
+                continue;
+            }
+            let kids = needs_children.then(|| children.remove(&pid).unwrap_or_default());
+            let observed = self.tracker.observe(pid, Observation::from_process(process, now, kids));
+            if needs_anomalies {
+                let before = self.anomalies.len();
+                self.anomalies.extend(observed.anomalies.iter().filter(|a| a.onset).map(|a| AnomalyEvent {
+                    pid,
+                    name: process.name().to_string(),
+                    at: now,
+                    anomaly: a.clone(),
+                }));
+                self.report.anomaly_count += self.anomalies.len() - before;
+            }
+            if watches_exit {
+                seen.insert(pid, SeenProcess {
+                    name: process.name().to_string(),
+                    start_time: process.start_time(),
+                    cmd: process.cmd().join(" "),
+                    cpu_usage: observed.cpu_usage,
+                    memory_bytes: observed.memory_bytes,
+                });
+            }
+            metrics.insert(pid, observed);
+        }
+        self.tracker.retain(&self.alive_pids);
+
+        for rule in &self.rules {
+            let Some(target) = &rule.target else { continue }; // validado en new/reload
+
+            for (pid, process) in self.system.processes() {
+                let pid = pid.as_u32();
+                if !target.matches(pid, process.name()) {
+                    continue;
+                }
+                let Some(process_metrics) = metrics.get(&pid) else { continue };
+
+                let cpu_usage = process_metrics.cpu_usage;
+                let memory = process_metrics.memory_bytes;
+                let Some(condition) = rule.triggered_condition(process_metrics) else {
+                    continue;
+                };
+
+                let key = (rule.name.clone(), pid);
+                still_active.insert(key.clone());
+                let incident = self.active.entry(key).or_insert_with(|| Incident {
+                    alert_id: None,
+                    rule: rule.name.clone(),
+                    pid,
+                    name: process.name().to_string(),
+                    start_time: process.start_time(),
+                    cmd: process.cmd().join(" "),
+                    condition: condition.to_string(),
+                    cpu_usage,
+                    memory_bytes: memory,
+                    peak_cpu_usage: cpu_usage,
+                    peak_memory_bytes: memory,
+                    actions: Vec::new(),
+                    escalation_level: 0,
+                    since: now,
+                    ticks: 0,
+                });
+                incident.ticks += 1;
+                incident.peak_cpu_usage = incident.peak_cpu_usage.max(cpu_usage);
+                incident.peak_memory_bytes = incident.peak_memory_bytes.max(memory);
+
+                let (metric, value) = condition.observed(process_metrics);
+                let ctx = AlertContext {
+                    rule: rule.name.clone(),
+                    pid,
+                    name: process.name().to_string(),
+                    condition: condition.to_string(),
+                    metric,
+                    value,
+                    cpu_usage,
+                    memory_bytes: memory,
+                    triggered_at: now,
+                };
+
+                let elapsed = (now - incident.since).to_std().unwrap_or_default();
+                let due = rule.actions_due(elapsed, &mut incident.escalation_level, incident.ticks == 1);
+                run_actions(due, &ctx, condition, incident, self.notifiers, &mut self.report, &mut fired)?;
+
+                if let Some(conn) = self.store.as_mut() {
+                    incident.persist(conn)?;
+                }
+            }
+        }
+
+        // Los procesos vigilados en la iteración anterior que ya no están (un PID
+        // reutilizado cuenta como terminado) disparan las reglas con `exit`
+        let previous = std::mem::replace(&mut self.last_seen, seen);
+        for (pid, gone) in previous {
+            if self.last_seen.get(&pid).is_some_and(|p| p.start_time == gone.start_time) {
+                continue;
+            }
+            self.fire_exit(pid, gone, now, &mut fired)?;
+        }
+
+        // Los incidentes cuya condición dejó de cumplirse (o cuyo proceso terminó) se cierran
+        let (open, closed): (HashMap<_, _>, HashMap<_, _>) =
+            std::mem::take(&mut self.active).into_iter().partition(|(key, _)| still_active.contains(key));
+        self.active = open;
+        close_incidents(&mut self.store, closed.values())?;
+
+        Ok(fired)
+    }
+
+    /// Dispara las reglas con `exit` que vigilaban un proceso que terminó. El
+    /// incidente se abre y se cierra en la misma iteración; `kill` y `renice`
+    /// se omiten porque ya no hay proceso sobre el que actuar.
+    fn fire_exit(
+        &mut self,
+        pid: u32,
+        gone: SeenProcess,
+        now: DateTime<Utc>,
+        fired: &mut Vec<(AlertContext, Action)>,
+    ) -> CoreResult<()> {
+        let condition = Condition::ProcessExit;
+        for rule in &self.rules {
+            let matches = rule.target.as_ref().is_some_and(|t| t.matches(pid, &gone.name));
+            if !matches || !rule.conditions.iter().any(|c| matches!(c, Condition::ProcessExit)) {
+                continue;
+            }
+
+            let mut incident = Incident {
+                alert_id: None,
+                rule: rule.name.clone(),
+                pid,
+                name: gone.name.clone(),
+                start_time: gone.start_time,
+                cmd: gone.cmd.clone(),
+                condition: condition.to_string(),
+                cpu_usage: gone.cpu_usage,
+                memory_bytes: gone.memory_bytes,
+                peak_cpu_usage: gone.cpu_usage,
+                peak_memory_bytes: gone.memory_bytes,
+                actions: Vec::new(),
+                escalation_level: 0,
+                since: now,
+                ticks: 1,
+            };
+            let ctx = AlertContext {
+                rule: rule.name.clone(),
+                pid,
+                name: gone.name.clone(),
+                condition: condition.to_string(),
+                metric: "exit".into(),
+                value: None,
+                cpu_usage: gone.cpu_usage,
+                memory_bytes: gone.memory_bytes,
+                triggered_at: now,
+            };
+
+            let due: Vec<&Action> = rule
+                .actions_due(Duration::ZERO, &mut incident.escalation_level, true)
+                .into_iter()
+                .filter(|action| !matches!(action, Action::Kill | Action::Renice(_)))
+                .collect();
+            run_actions(due, &ctx, &condition, &mut incident, self.notifiers, &mut self.report, fired)?;
+
+            if let Some(conn) = self.store.as_mut() {
+                incident.persist(conn)?;
+            }
+            close_incidents(&mut self.store, std::iter::once(&incident))?;
+        }
+        Ok(())
+    }
+
+    /// Cierra los incidentes abiertos y devuelve el reporte final.
+    pub fn finish(mut self) -> CoreResult<WatchdogReport> {
+        close_incidents(&mut self.store, self.active.values())?;
+        Ok(self.into_report())
+    }
+
+    pub fn into_report(mut self) -> WatchdogReport {
+        self.report.duration = self.start.elapsed();
+        self.report
+    }
+}
+
+/// Comprueba que las reglas tengan nombre único, objetivo y canales configurados.
+pub fn validate_rules(rules: &[Rule], notifiers: &NotifierRegistry) -> CoreResult<()> {
+    let mut names = HashSet::new();
+    for rule in rules {
+        if !names.insert(rule.name.as_str()) {
+            return Err(CoreError::WatchdogError(format!("Regla duplicada: '{}'", rule.name)));
+        }
+        if rule.target.is_none() {
+            return Err(CoreError::WatchdogError(format!("La regla '{}' no indica a qué procesos se aplica", rule.name)));
+        }
+        // Los canales de los pasos de escalado también: fallar al cargar es
+        // mejor que descubrirlo cuando el incidente ya lleva minutos abierto
+        let escalation = rule.escalation.iter().flat_map(|step| &step.actions);
+        for action in rule.actions.iter().chain(escalation) {
+            if let Action::Notify(channel) = action {
+                if !notifiers.contains(channel) {
+                    return Err(CoreError::WatchdogError(format!(
+                        "La regla '{}' usa el canal no configurado '{}'",
+                        rule.name, channel
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::types::EscalationStep;
+
+    fn rule(actions: Vec<Action>, escalation: Vec<EscalationStep>) -> Rule {
+        Rule {
+            name: "runaway".into(),
+            target: Some(Target::Pid(1234)),
+            conditions: vec!["cpu > 90".parse().unwrap()],
+            actions,
+            escalation,
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_channels_in_escalation_steps() {
+        let notifiers = NotifierRegistry::default();
+        let step = |actions| EscalationStep { after: Duration::from_secs(60), actions };
+
+        assert!(validate_rules(&[rule(vec![Action::Kill], vec![step(vec![Action::Kill])])], &notifiers).is_ok());
+        for rules in [
+            vec![rule(vec![Action::Notify("oncall".into())], vec![])],
+            vec![rule(vec![Action::Kill], vec![step(vec![Action::Notify("oncall".into())])])],
+        ] {
+            let err = validate_rules(&rules, &notifiers).unwrap_err();
+            assert!(err.to_string().contains("'oncall'"), "{err}");
+        }
+    }
+}
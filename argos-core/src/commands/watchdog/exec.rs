@@ -0,0 +1,142 @@
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::commands::types::AlertContext;
+
+/// Timeout por defecto de un hook `exec` si no se especifica otro.
+pub const DEFAULT_EXEC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Resultado de ejecutar un hook `exec` cuando se dispara una regla.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecResult {
+    pub rule: String,
+    pub pid: u32,
+    pub command: String,
+    pub exit_code: Option<i32>, // None si terminó por señal o por timeout
+    pub timed_out: bool,
+    pub duration_ms: u64,
+    pub error: Option<String>, // el comando no se pudo lanzar o esperar
+}
+
+impl ExecResult {
+    pub fn success(&self) -> bool {
+        self.error.is_none() && !self.timed_out && self.exit_code == Some(0)
+    }
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    // En su propio grupo de procesos, para poder matar también lo que lance la shell
+    cmd.args(["-c", command]).process_group(0);
+    cmd
+}
+
+#[cfg(not(unix))]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.args(["/C", command]);
+    cmd
+}
+
+/// Mata el hook junto con los procesos que haya lanzado (`sleep 600; curl ...`,
+/// tuberías): matar solo la shell los dejaría huérfanos y en marcha.
+#[cfg(unix)]
+fn kill_hook(child: &mut Child) {
+    unsafe { libc::killpg(child.id() as libc::pid_t, libc::SIGKILL) };
+}
+
+#[cfg(not(unix))]
+fn kill_hook(child: &mut Child) {
+    let _ = child.kill();
+}
+
+/// Ejecuta `command` en una shell pasando el contexto de la alerta como
+/// variables de entorno `ARGOS_*` y como JSON por stdin.
+/// Si el comando supera `timeout` se mata (con todo lo que haya lanzado) y se
+/// marca como `timed_out`; si no se puede lanzar, el fallo queda en `error` y
+/// el watchdog sigue.
+pub fn run_exec(command: &str, timeout: Duration, ctx: &AlertContext) -> ExecResult {
+    let start = Instant::now();
+    let mut result = ExecResult {
+        rule: ctx.rule.clone(),
+        pid: ctx.pid,
+        command: command.to_string(),
+        exit_code: None,
+        timed_out: false,
+        duration_ms: 0,
+        error: None,
+    };
+    if let Err(e) = wait_exec(command, timeout, ctx, &mut result) {
+        result.error = Some(format!("No se pudo ejecutar '{}': {}", command, e));
+    }
+    result.duration_ms = start.elapsed().as_millis() as u64;
+    result
+}
+
+fn wait_exec(command: &str, timeout: Duration, ctx: &AlertContext, result: &mut ExecResult) -> std::io::Result<()> {
+    let payload = serde_json::to_string(ctx)?;
+    let start = Instant::now();
+
+    let mut child = shell_command(command)
+        .env("ARGOS_RULE", &ctx.rule)
+        .env("ARGOS_PID", ctx.pid.to_string())
+        .env("ARGOS_NAME", &ctx.name)
+        .env("ARGOS_CONDITION", &ctx.condition)
+        .env("ARGOS_METRIC", &ctx.metric)
+        .env("ARGOS_VALUE", ctx.value.as_deref().unwrap_or_default())
+        .env("ARGOS_CPU", format!("{:.2}", ctx.cpu_usage))
+        .env("ARGOS_MEM_BYTES", ctx.memory_bytes.to_string())
+        .stdin(Stdio::piped())
+        .spawn()?;
+
+    // El comando puede no leer stdin; un pipe roto no es un error del hook
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(payload.as_bytes());
+    }
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            result.exit_code = status.code();
+            return Ok(());
+        }
+        if start.elapsed() >= timeout {
+            result.timed_out = true;
+            kill_hook(&mut child);
+            let _ = child.wait();
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notify::test_alert;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn timeout_kills_what_the_hook_started() {
+        let pid_file = std::env::temp_dir().join(format!("argos-exec-{}.pid", std::process::id()));
+        let command = format!("sleep 30 & echo $! > {}; wait", pid_file.display());
+
+        let result = run_exec(&command, Duration::from_millis(300), &test_alert());
+        assert!(result.timed_out);
+
+        let pid = std::fs::read_to_string(&pid_file).unwrap().trim().to_string();
+        std::fs::remove_file(&pid_file).unwrap();
+        // Ya no existe, o solo queda el zombi a la espera de que lo recojan
+        std::thread::sleep(Duration::from_millis(100));
+        let alive = match std::fs::read_to_string(format!("/proc/{}/stat", pid)) {
+            Ok(stat) => !stat.rsplit(')').next().unwrap_or_default().trim_start().starts_with('Z'),
+            Err(_) => false,
+        };
+        assert!(!alive, "el proceso {pid} sigue en marcha");
+    }
+}
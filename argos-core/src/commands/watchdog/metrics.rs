@@ -0,0 +1,134 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use chrono::{DateTime, Utc};
+
+use crate::anomaly::AnomalyDetector;
+use crate::commands::types::ProcessMetrics;
+use crate::process::procfs::{open_fd_count, thread_count};
+
+/// Ventana sobre la que se calculan las tasas por minuto.
+const RATE_WINDOW_SECS: f64 = 60.0;
+/// Tramo mínimo para estimar el crecimiento de memoria; con menos, un pico
+/// puntual (p. ej. al arrancar) se extrapola a cifras enormes por minuto.
+const MIN_GROWTH_SPAN_SECS: f64 = 10.0;
+
+/// Lo que se observa de un proceso en un instante; con el historial del
+/// [`MetricsTracker`] se completan las métricas derivadas (tasas).
+#[derive(Debug, Clone)]
+pub struct Observation {
+    pub at: DateTime<Utc>,
+    pub metrics: ProcessMetrics,
+    pub disk_totals: Option<(u64, u64)>, // bytes leídos y escritos desde que arrancó
+    pub children: Option<HashSet<u32>>,  // hijos vivos (sin contar hilos), si se conocen
+}
+
+impl Observation {
+    /// Lee las métricas de un proceso de sysinfo (y de `/proc` cuando hace falta).
+    pub fn from_process(process: &sysinfo::Process, at: DateTime<Utc>, children: Option<HashSet<u32>>) -> Self {
+        let pid = process.pid().as_u32();
+        let disk = process.disk_usage();
+        Self {
+            at,
+            metrics: ProcessMetrics {
+                cpu_usage: process.cpu_usage(),
+                memory_bytes: process.memory(),
+                virtual_memory_bytes: process.virtual_memory(),
+                open_fds: open_fd_count(pid),
+                threads: thread_count(pid),
+                state: format!("{:?}", process.status()),
+                runtime_secs: process.run_time(),
+                ..Default::default()
+            },
+            disk_totals: Some((disk.total_read_bytes, disk.total_written_bytes)),
+            children,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct History {
+    memory: VecDeque<(DateTime<Utc>, u64)>,
+    disk: Option<(DateTime<Utc>, u64, u64)>,
+    children: Option<HashSet<u32>>,
+    spawns: VecDeque<DateTime<Utc>>,
+    first_seen: Option<DateTime<Utc>>,
+}
+
+fn seconds_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_milliseconds() as f64 / 1000.0
+}
+
+/// Historial por PID para calcular tasas de E/S, crecimiento de memoria y
+/// creación de hijos, y líneas base para detectar anomalías. Lo comparten el
+/// watchdog y el backtest.
+#[derive(Debug, Default)]
+pub struct MetricsTracker {
+    history: HashMap<u32, History>,
+    anomalies: AnomalyDetector,
+}
+
+impl MetricsTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registra la observación y devuelve las métricas con las tasas calculadas.
+    pub fn observe(&mut self, pid: u32, obs: Observation) -> ProcessMetrics {
+        let Observation { at, mut metrics, disk_totals, children } = obs;
+        let history = self.history.entry(pid).or_default();
+        let first_seen = *history.first_seen.get_or_insert(at);
+
+        // E/S: diferencia de contadores respecto a la observación anterior
+        if let Some((read, written)) = disk_totals {
+            if let Some((prev_at, prev_read, prev_written)) = history.disk {
+                let secs = seconds_between(prev_at, at);
+                if secs > 0.0 {
+                    metrics.disk_read_per_sec = Some(read.saturating_sub(prev_read) as f64 / secs);
+                    metrics.disk_write_per_sec = Some(written.saturating_sub(prev_written) as f64 / secs);
+                }
+            }
+            history.disk = Some((at, read, written));
+        }
+
+        // Memoria: pendiente entre la observación más antigua de la ventana y la actual
+        // (se conserva siempre la anterior para que funcione con muestras espaciadas)
+        history.memory.push_back((at, metrics.memory_bytes));
+        while history.memory.len() > 2 && seconds_between(history.memory[1].0, at) >= RATE_WINDOW_SECS {
+            history.memory.pop_front();
+        }
+        if let Some(&(oldest_at, oldest)) = history.memory.front() {
+            let secs = seconds_between(oldest_at, at);
+            if secs >= MIN_GROWTH_SPAN_SECS {
+                metrics.memory_growth_per_min = Some((metrics.memory_bytes as f64 - oldest as f64) / secs * 60.0);
+            }
+        }
+
+        // Hijos: los que no estaban en la observación anterior cuentan como creados.
+        // Los hijos que nacen y terminan entre dos iteraciones no se ven.
+        if let Some(children) = children {
+            if let Some(known) = &history.children {
+                let spawned = children.difference(known).count();
+                history.spawns.extend(std::iter::repeat_n(at, spawned));
+            }
+            history.children = Some(children);
+            while history.spawns.front().is_some_and(|t| seconds_between(*t, at) > RATE_WINDOW_SECS) {
+                history.spawns.pop_front();
+            }
+
+            let observed = seconds_between(first_seen, at).min(RATE_WINDOW_SECS);
+            if observed > 0.0 {
+                metrics.spawn_rate_per_min = Some(history.spawns.len() as f64 / observed * 60.0);
+            }
+        }
+
+        // Con las tasas ya calculadas, para que también cuenten las de E/S
+        metrics.anomalies = self.anomalies.observe(pid, &metrics);
+        metrics
+    }
+
+    /// Olvida el historial de los procesos que ya no existen.
+    pub fn retain(&mut self, alive: &HashSet<u32>) {
+        self.history.retain(|pid, _| alive.contains(pid));
+        self.anomalies.retain(alive);
+    }
+}
@@ -1,44 +1,41 @@
-use std::time::{Duration, Instant};
-use crate::commands::types::{Condition, Action};
+pub mod backtest;
+pub mod exec;
+pub mod metrics;
+pub mod engine;
+pub mod rules;
+
+use std::time::Duration;
+use crate::commands::types::{Condition, Action, Rule, Target};
 use crate::errors::CoreError;
-use sysinfo::System;
+use crate::notify::{NotifierRegistry, NotifyResult};
+use engine::Watchdog;
+use exec::ExecResult;
 
 pub struct WatchdogReport {
     pub pid: u32,
     pub triggered: Vec<(Condition, Action)>, // historial de disparos
+    pub exec_results: Vec<ExecResult>,       // estado de salida de los hooks exec
+    pub notifications: Vec<NotifyResult>,    // entregas a canales de notificación
+    pub anomaly_count: usize,                // comienzos de anomalía, si alguna regla usa `anomalous(...)`
     pub duration: Duration,                  // cuánto tiempo estuvo corriendo
 }
 
+/// Vigila `pid` en primer plano hasta que termina. Las reglas sin objetivo se aplican a `pid`.
 pub fn watchdog(
     pid: u32,
     interval: Duration,
-    conditions: Vec<Condition>,
-    actions: Vec<Action>,
+    rules: Vec<Rule>,
+    notifiers: &NotifierRegistry,
 ) -> Result<WatchdogReport, CoreError> {
-    let mut system = System::new_all();
-    let start = Instant::now();
-
-    let mut triggered_log: Vec<(Condition, Action)> = Vec::new();
+    let rules = rules
+        .into_iter()
+        .map(|rule| Rule { target: rule.target.or(Some(Target::Pid(pid))), ..rule })
+        .collect();
+    let mut engine = Watchdog::new(rules, notifiers)?;
 
     loop {
-        system.refresh_process(sysinfo::Pid::from(pid as usize));
-
-        if let Some(process) = system.process(sysinfo::Pid::from(pid as usize)) {
-            let cpu_usage = process.cpu_usage();
-            let memory = process.memory();
-
-            for condition in &conditions {
-                if condition.is_triggered(cpu_usage, memory) {
-                    for action in &actions {
-                        // Ejecutar acción
-                        action.execute(process).map_err(|e| CoreError::WatchdogError(format!("Action execution failed: {}", e)))?;
-
-                        // Guardar en historial
-                        triggered_log.push((condition.clone(), action.clone()));
-                    }
-                }
-            }
-        } else {
+        engine.tick()?;
+        if !engine.has_live_targets() {
             println!("Proceso {} no encontrado", pid);
             break; // termina el watchdog
         }
@@ -46,9 +43,5 @@ pub fn watchdog(
         std::thread::sleep(interval);
     }
 
-    Ok(WatchdogReport {
-        pid,
-        triggered: triggered_log,
-        duration: start.elapsed(),
-    })
+    Ok(engine.into_report())
 }
@@ -0,0 +1,108 @@
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::commands::types::{parse_duration_secs, Action, Condition, EscalationStep, Rule, Target};
+use crate::errors::{CoreError, CoreResult};
+
+/// Archivo de reglas en TOML:
+///
+/// ```toml
+/// [[rule]]
+/// name = "postgres-mem"
+/// process = "postgres*"
+/// conditions = ["mem > 2G"]
+/// actions = ["log", "notify:oncall"]
+///
+/// [[rule]]
+/// name = "runaway"
+/// process = "worker*"
+/// conditions = ["cpu > 90"]
+/// escalation = [
+///   { after = "0s", actions = ["log"] },
+///   { after = "1m", actions = ["notify:oncall"] },
+///   { after = "5m", actions = ["renice:10"] },
+///   { after = "10m", actions = ["kill"] },
+/// ]
+/// ```
+#[derive(Debug, Deserialize)]
+struct RuleFile {
+    #[serde(default, rename = "rule")]
+    rules: Vec<RuleSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleSpec {
+    name: String,
+    pid: Option<u32>,
+    process: Option<String>,
+    conditions: Vec<String>,
+    #[serde(default)]
+    actions: Vec<String>,
+    #[serde(default)]
+    escalation: Vec<StepSpec>,
+    exec_timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StepSpec {
+    after: String,
+    actions: Vec<String>,
+}
+
+impl RuleSpec {
+    fn into_rule(self) -> CoreResult<Rule> {
+        let invalid = |e: String| CoreError::ValidationError(format!("Regla '{}': {}", self.name, e));
+
+        let target = match (self.pid, &self.process) {
+            (Some(_), Some(_)) => return Err(invalid("indique `pid` o `process`, no ambos".into())),
+            (Some(pid), None) => Some(Target::Pid(pid)),
+            (None, Some(pattern)) => Some(Target::Name(pattern.clone())),
+            (None, None) => None,
+        };
+
+        let conditions = self
+            .conditions
+            .iter()
+            .map(|c| c.parse::<Condition>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(invalid)?;
+
+        let parse_actions = |actions: &[String]| -> CoreResult<Vec<Action>> {
+            Ok(actions
+                .iter()
+                .map(|a| a.parse::<Action>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(invalid)?
+                .into_iter()
+                .map(|action| match (action, self.exec_timeout_secs) {
+                    (Action::Exec { command, .. }, Some(secs)) => Action::Exec { command, timeout: Duration::from_secs(secs) },
+                    (other, _) => other,
+                })
+                .collect())
+        };
+        let actions = parse_actions(&self.actions)?;
+
+        let mut escalation = Vec::new();
+        for step in &self.escalation {
+            let after = parse_duration_secs(&step.after)
+                .ok_or_else(|| invalid(format!("duración de escalado inválida: {}", step.after)))?;
+            escalation.push(EscalationStep { after: Duration::from_secs(after), actions: parse_actions(&step.actions)? });
+        }
+        if escalation.windows(2).any(|w| w[0].after > w[1].after) {
+            return Err(invalid("los pasos de `escalation` deben estar ordenados por `after`".into()));
+        }
+
+        Ok(Rule { name: self.name, target, conditions, actions, escalation })
+    }
+}
+
+/// Lee un conjunto de reglas desde un archivo TOML.
+pub fn load_rules(path: &Path) -> CoreResult<Vec<Rule>> {
+    let content = std::fs::read_to_string(path)?;
+    let file: RuleFile = toml::from_str(&content)
+        .map_err(|e| CoreError::ValidationError(format!("Archivo de reglas inválido {}: {}", path.display(), e)))?;
+
+    file.rules.into_iter().map(RuleSpec::into_rule).collect()
+}
@@ -0,0 +1,169 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::CoreResult,
+    process::{model::ProcessRow, procfs::session_id, reader::ProcessReader},
+};
+
+/// Zombie con el tiempo desde que arrancó: es una cota superior de lo que
+/// lleva sin recoger (el kernel no guarda cuándo terminó).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZombieProcess {
+    pub process: ProcessRow,
+    pub age_secs: u64,
+}
+
+/// Zombies de un mismo padre, que es quien debe recogerlos con `wait()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZombieGroup {
+    pub parent_pid: Option<u32>,
+    pub parent: Option<ProcessRow>, // `None` si el padre ya no aparece
+    pub zombies: Vec<ZombieProcess>,
+    pub oldest_age_secs: u64,
+    pub suggestion: String,
+}
+
+/// Quién adoptó a un huérfano al terminar su padre.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Adopter {
+    Init,
+    Subreaper,
+}
+
+/// Proceso vivo que quedó huérfano y fue adoptado por PID 1 o por un subreaper.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanProcess {
+    pub process: ProcessRow,
+    pub adopter: Adopter,
+    pub adopter_pid: u32,
+    pub adopter_name: String,
+    pub session_id: u32,
+    pub age_secs: u64,
+    pub suggestion: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZombieReport {
+    pub captured_at: DateTime<Utc>,
+    pub zombie_count: usize,
+    pub groups: Vec<ZombieGroup>,
+    pub orphans: Vec<OrphanProcess>,
+}
+
+fn age_secs(row: &ProcessRow, now: &DateTime<Utc>) -> u64 {
+    (now.timestamp() as u64).saturating_sub(row.start_time)
+}
+
+fn zombie_suggestion(parent_pid: Option<u32>, parent: Option<&ProcessRow>) -> String {
+    match (parent_pid, parent) {
+        (_, None) => "El padre ya no existe: init debería adoptarlos y recogerlos en breve".to_string(),
+        (Some(1), Some(_)) => "Los adoptó PID 1 y no los recoge (p. ej. un contenedor sin init): use un init mínimo como `tini` o `docker run --init`".to_string(),
+        (_, Some(p)) if p.state == "Stop" || p.state == "Tracing" => format!(
+            "El padre {} está detenido y no puede recogerlos: reanúdelo con `kill -CONT {}`",
+            p.name, p.pid
+        ),
+        (_, Some(p)) => format!(
+            "{} no llama a wait() por sus hijos: pruebe `kill -CHLD {}`; si siguen, reinicie el padre (al terminar, init adopta y recoge los zombies)",
+            p.name, p.pid
+        ),
+    }
+}
+
+fn orphan_suggestion(adopter: Adopter, adopter_name: &str, adopter_pid: u32) -> String {
+    match adopter {
+        Adopter::Init => "Su padre terminó sin él: si no debe seguir en marcha, termínelo con `kill`; si debe, póngalo bajo un supervisor (systemd, supervisord)".to_string(),
+        Adopter::Subreaper => format!(
+            "Lo adoptó el subreaper {} ({}), que lo vigila: si no debe seguir en marcha, termínelo con `kill`",
+            adopter_name, adopter_pid
+        ),
+    }
+}
+
+/// Agrupa los zombies por padre, de los que más acumulan a los que menos.
+fn zombie_groups(zombies: &[ProcessRow], by_pid: &HashMap<u32, &ProcessRow>, now: &DateTime<Utc>) -> Vec<ZombieGroup> {
+    let mut by_parent: HashMap<Option<u32>, Vec<ZombieProcess>> = HashMap::new();
+    for zombie in zombies {
+        by_parent
+            .entry(zombie.parent_pid)
+            .or_default()
+            .push(ZombieProcess { process: zombie.clone(), age_secs: age_secs(zombie, now) });
+    }
+
+    let mut groups: Vec<ZombieGroup> = by_parent
+        .into_iter()
+        .map(|(parent_pid, mut zombies)| {
+            zombies.sort_by_key(|z| std::cmp::Reverse(z.age_secs));
+            let parent = parent_pid.and_then(|p| by_pid.get(&p)).map(|p| (*p).clone());
+            ZombieGroup {
+                suggestion: zombie_suggestion(parent_pid, parent.as_ref()),
+                oldest_age_secs: zombies.first().map_or(0, |z| z.age_secs),
+                parent_pid,
+                parent,
+                zombies,
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| b.zombies.len().cmp(&a.zombies.len()).then(b.oldest_age_secs.cmp(&a.oldest_age_secs)));
+    groups
+}
+
+/// Huérfanos adoptados. No hay registro del padre original, así que se
+/// deducen de la sesión: un proceso que no lidera su sesión y cuyo padre es de
+/// otra sesión no lo lanzó ese padre. Con PID 1 como padre basta con eso; con
+/// otro padre (un subreaper) se exige además que el líder de la sesión ya no
+/// exista. Los demonios que crean su propia sesión no cuentan como huérfanos.
+fn orphans(rows: &[ProcessRow], by_pid: &HashMap<u32, &ProcessRow>, now: &DateTime<Utc>) -> Vec<OrphanProcess> {
+    let sessions: HashMap<u32, u32> = rows.iter().filter_map(|r| session_id(r.pid).map(|s| (r.pid, s))).collect();
+    let alive: HashSet<u32> = rows.iter().filter(|r| r.state != "Zombie").map(|r| r.pid).collect();
+
+    let mut orphans: Vec<OrphanProcess> = rows
+        .iter()
+        .filter(|r| r.state != "Zombie" && r.pid != 1)
+        .filter_map(|row| {
+            let parent = by_pid.get(&row.parent_pid?)?;
+            let session = *sessions.get(&row.pid)?;
+            // Sesión 0: creada fuera del espacio de PIDs, no se sabe quién la lidera
+            if session == 0 || session == row.pid || sessions.get(&parent.pid) == Some(&session) {
+                return None;
+            }
+            let adopter = if parent.pid == 1 {
+                Adopter::Init
+            } else if !alive.contains(&session) {
+                Adopter::Subreaper
+            } else {
+                return None;
+            };
+            Some(OrphanProcess {
+                suggestion: orphan_suggestion(adopter, &parent.name, parent.pid),
+                process: row.clone(),
+                adopter,
+                adopter_pid: parent.pid,
+                adopter_name: parent.name.clone(),
+                session_id: session,
+                age_secs: age_secs(row, now),
+            })
+        })
+        .collect();
+    orphans.sort_by_key(|o| (std::cmp::Reverse(o.age_secs), o.process.pid));
+    orphans
+}
+
+/// Zombies agrupados por padre y huérfanos adoptados, de una misma lectura.
+pub fn find_zombies() -> CoreResult<ZombieReport> {
+    let mut reader = ProcessReader::new();
+    let zombies = reader.get_zombies();
+    let rows = reader.current_processes();
+    let captured_at = Utc::now();
+    let by_pid: HashMap<u32, &ProcessRow> = rows.iter().map(|r| (r.pid, r)).collect();
+
+    Ok(ZombieReport {
+        zombie_count: zombies.len(),
+        groups: zombie_groups(&zombies, &by_pid, &captured_at),
+        orphans: orphans(&rows, &by_pid, &captured_at),
+        captured_at,
+    })
+}
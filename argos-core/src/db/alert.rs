@@ -0,0 +1,85 @@
+use diesel::prelude::*;
+use crate::db::schema::alerts;
+use crate::models::{Alert, NewAlert};
+
+/// Inserta una alerta y devuelve su id.
+pub fn insert_alert(conn: &mut SqliteConnection, alert: &NewAlert) -> QueryResult<i32> {
+    diesel::insert_into(alerts::table)
+        .values(alert)
+        .returning(alerts::id)
+        .get_result(conn)
+}
+
+/// Actualiza los máximos y las acciones de una alerta activa.
+pub fn update_alert_progress(
+    conn: &mut SqliteConnection,
+    id_val: i32,
+    peak_cpu: f32,
+    peak_memory: i64,
+    actions_json: &str,
+) -> QueryResult<usize> {
+    diesel::update(alerts::table.filter(alerts::id.eq(id_val)))
+        .set((
+            alerts::peak_cpu_usage.eq(peak_cpu),
+            alerts::peak_memory_bytes.eq(peak_memory),
+            alerts::actions.eq(actions_json),
+        ))
+        .execute(conn)
+}
+
+pub fn close_alert(conn: &mut SqliteConnection, id_val: i32, ended: chrono::NaiveDateTime) -> QueryResult<usize> {
+    diesel::update(alerts::table.filter(alerts::id.eq(id_val)))
+        .set(alerts::ended_at.eq(Some(ended)))
+        .execute(conn)
+}
+
+pub fn ack_alert(
+    conn: &mut SqliteConnection,
+    id_val: i32,
+    acked: chrono::NaiveDateTime,
+    note: Option<&str>,
+) -> QueryResult<usize> {
+    diesel::update(alerts::table.filter(alerts::id.eq(id_val)))
+        .set((alerts::acked_at.eq(Some(acked)), alerts::ack_note.eq(note)))
+        .execute(conn)
+}
+
+pub fn get_alert_by_id(conn: &mut SqliteConnection, id_val: i32) -> QueryResult<Alert> {
+    alerts::table
+        .filter(alerts::id.eq(id_val))
+        .select(Alert::as_select())
+        .first(conn)
+}
+
+/// Filtros para listar alertas; los campos en `None`/`false` no filtran.
+#[derive(Debug, Default, Clone)]
+pub struct AlertFilter {
+    pub rule: Option<String>,
+    pub pid: Option<i32>,
+    pub active_only: bool,
+    pub unacked_only: bool,
+    pub limit: i64,
+}
+
+/// Lista alertas de la más reciente a la más antigua.
+pub fn list_alerts(conn: &mut SqliteConnection, filter: &AlertFilter) -> QueryResult<Vec<Alert>> {
+    let mut query = alerts::table.select(Alert::as_select()).into_boxed();
+
+    if let Some(rule) = &filter.rule {
+        query = query.filter(alerts::rule.eq(rule.clone()));
+    }
+    if let Some(pid) = filter.pid {
+        query = query.filter(alerts::pid.eq(pid));
+    }
+    if filter.active_only {
+        query = query.filter(alerts::ended_at.is_null());
+    }
+    if filter.unacked_only {
+        query = query.filter(alerts::acked_at.is_null());
+    }
+
+    query
+        .order(alerts::started_at.desc())
+        .limit(filter.limit)
+        .load(conn)
+}
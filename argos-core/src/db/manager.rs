@@ -1,11 +1,34 @@
 use diesel::prelude::*;
 use diesel::sqlite::SqliteConnection;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 use std::env;
 use dotenvy::dotenv;
 
+use crate::errors::{CoreError, CoreResult};
+
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("../migrations");
+
 pub fn establish_connection() -> SqliteConnection {
     dotenv().ok();
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL debe estar definida");
     SqliteConnection::establish(&database_url)
         .unwrap_or_else(|_| panic!("Error conectando a {}", database_url))
 }
+
+/// Abre la base de datos (`database_url` o, si no se indica, `DATABASE_URL`)
+/// y aplica las migraciones pendientes.
+pub fn connect(database_url: Option<&str>) -> CoreResult<SqliteConnection> {
+    dotenv().ok();
+    let database_url = match database_url {
+        Some(url) => url.to_string(),
+        None => env::var("DATABASE_URL")
+            .map_err(|_| CoreError::DatabaseError("DATABASE_URL debe estar definida".into()))?,
+    };
+
+    let mut conn = SqliteConnection::establish(&database_url)
+        .map_err(|e| CoreError::DatabaseError(format!("Error conectando a {}: {}", database_url, e)))?;
+    conn.run_pending_migrations(MIGRATIONS)
+        .map_err(|e| CoreError::DatabaseError(format!("Error aplicando migraciones: {}", e)))?;
+
+    Ok(conn)
+}
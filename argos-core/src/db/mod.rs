@@ -2,4 +2,5 @@ pub mod schema;
 pub mod process;
 pub mod log_session;
 pub mod sample;
+pub mod alert;
 pub mod manager;
\ No newline at end of file
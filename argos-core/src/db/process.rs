@@ -8,6 +8,13 @@ pub fn insert_process(conn: &mut SqliteConnection, process: &Process) -> QueryRe
         .execute(conn)
 }
 
+/// Inserta el proceso o reemplaza el registro existente con ese PID.
+pub fn replace_process(conn: &mut SqliteConnection, process: &Process) -> QueryResult<usize> {
+    diesel::replace_into(processes::table)
+        .values(process)
+        .execute(conn)
+}
+
 pub fn get_process_by_pid(conn: &mut SqliteConnection, pid_val: i32) -> QueryResult<Process> {
     processes::table
         .filter(processes::pid.eq(pid_val))
@@ -1,6 +1,6 @@
 use diesel::prelude::*;
 use crate::db::schema::*; // or specify the correct table/module if different
-use crate::models::Sample; // Adjust the path according to your project structure
+use crate::models::{LogSession, Sample};
 
 pub fn insert_sample(conn: &mut SqliteConnection, sample: &Sample) -> QueryResult<usize> {
     diesel::insert_into(samples::table)
@@ -15,3 +15,14 @@ pub fn get_samples_by_log(conn: &mut SqliteConnection, log_id_val: &str) -> Quer
 }
 
 // Más funciones si necesitas
+
+/// Todas las muestras junto a su sesión y, si se registró, el nombre del proceso.
+pub fn get_samples_with_sessions(
+    conn: &mut SqliteConnection,
+) -> QueryResult<Vec<(Sample, LogSession, Option<String>)>> {
+    samples::table
+        .inner_join(log_sessions::table.left_join(processes::table))
+        .select((samples::all_columns, log_sessions::all_columns, processes::name.nullable()))
+        .order((log_sessions::started_at.asc(), samples::timestamp.asc()))
+        .load(conn)
+}
@@ -1,5 +1,26 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    alerts (id) {
+        id -> Integer,
+        rule -> Text,
+        pid -> Integer,
+        process_name -> Text,
+        process_start_time -> Nullable<BigInt>,
+        process_cmd -> Nullable<Text>,
+        condition -> Text,
+        cpu_usage -> Float,
+        memory_bytes -> BigInt,
+        peak_cpu_usage -> Float,
+        peak_memory_bytes -> BigInt,
+        actions -> Text,
+        started_at -> Timestamp,
+        ended_at -> Nullable<Timestamp>,
+        acked_at -> Nullable<Timestamp>,
+        ack_note -> Nullable<Text>,
+    }
+}
+
 diesel::table! {
     log_sessions (id) {
         id -> Text,
@@ -27,7 +48,14 @@ diesel::table! {
         log_id -> Text,
         timestamp -> Float,
         cpu_usage -> Float,
-        memory -> Integer,
+        memory -> BigInt,
+        pid -> Nullable<Integer>,
+        captured_at -> Nullable<Timestamp>,
+        monotonic_secs -> Nullable<Double>,
+        cpu_time_delta_ms -> Nullable<BigInt>,
+        read_bytes_delta -> Nullable<BigInt>,
+        write_bytes_delta -> Nullable<BigInt>,
+        process -> Nullable<Text>,
     }
 }
 
@@ -35,6 +63,7 @@ diesel::joinable!(log_sessions -> processes (process_pid));
 diesel::joinable!(samples -> log_sessions (log_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    alerts,
     log_sessions,
     processes,
     samples,
@@ -33,6 +33,18 @@ pub enum CoreError {
 
     #[error("Error de watchdog: {0}")]
     WatchdogError(String),
+
+    #[error("Error de notificación: {0}")]
+    NotifyError(String),
+
+    #[error("Error de base de datos: {0}")]
+    DatabaseError(String),
+}
+
+impl From<diesel::result::Error> for CoreError {
+    fn from(e: diesel::result::Error) -> Self {
+        CoreError::DatabaseError(e.to_string())
+    }
 }
 
 pub type CoreResult<T> = Result<T, CoreError>;
\ No newline at end of file
@@ -1,8 +1,10 @@
-
 pub mod process;
 pub mod commands;
 pub mod users;
 pub mod db;
 pub mod errors;
 pub mod validation;
-mod models;
\ No newline at end of file
+pub mod notify;
+pub mod models;
+pub mod stats;
+pub mod anomaly;
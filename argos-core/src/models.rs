@@ -1,6 +1,7 @@
 use diesel::prelude::*;
-use crate::db::schema::{processes, log_sessions, samples};
+use crate::db::schema::{alerts, processes, log_sessions, samples};
 use serde::{Serialize, Deserialize};
+use crate::process::model::SampleRecord;
 
 // Model para la tabla processes
 #[derive(Queryable, Insertable, Serialize, Deserialize, Debug)]
@@ -30,6 +31,7 @@ pub struct LogSession {
 pub struct NewLogSession<'a> {
     pub id: &'a str,
     pub process_pid: i32,
+    pub started_at: Option<chrono::NaiveDateTime>, // None: el momento de la inserción
     pub duration_secs: Option<i32>,
     pub iterations: Option<i32>,
 }
@@ -41,7 +43,75 @@ pub struct NewLogSession<'a> {
 pub struct Sample {
     pub id: Option<i32>,
     pub log_id: String,
-    pub timestamp: f32,
+    pub timestamp: f32,       // segundos desde el inicio de la sesión
     pub cpu_usage: f32,
-    pub memory: i32,
+    pub memory: i64,          // bytes
+    pub pid: Option<i32>,
+    pub captured_at: Option<chrono::NaiveDateTime>, // UTC
+    pub monotonic_secs: Option<f64>,
+    pub cpu_time_delta_ms: Option<i64>,
+    pub read_bytes_delta: Option<i64>,
+    pub write_bytes_delta: Option<i64>,
+    pub process: Option<String>, // JSON del ProcessRow
+}
+
+impl Sample {
+    /// Fila de `samples` para una muestra de la sesión `log_id`, que empezó con `first`.
+    pub fn from_record(log_id: &str, first: &SampleRecord, record: &SampleRecord) -> Self {
+        let as_i64 = |v: Option<u64>| v.map(|v| v.min(i64::MAX as u64) as i64);
+        Self {
+            id: None,
+            log_id: log_id.to_string(),
+            timestamp: (record.elapsed_secs - first.elapsed_secs) as f32,
+            cpu_usage: record.process.cpu_usage as f32,
+            memory: (record.process.memory_mb * 1_048_576.0).round() as i64,
+            pid: Some(record.process.pid as i32),
+            captured_at: Some(record.captured_at.naive_utc()),
+            monotonic_secs: Some(record.monotonic_secs),
+            cpu_time_delta_ms: as_i64(record.cpu_time_delta_ms),
+            read_bytes_delta: as_i64(record.read_bytes_delta),
+            write_bytes_delta: as_i64(record.write_bytes_delta),
+            process: serde_json::to_string(&record.process).ok(),
+        }
+    }
+}
+
+
+// Model para la tabla alerts
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = alerts)]
+pub struct Alert {
+    pub id: i32,
+    pub rule: String,
+    pub pid: i32,
+    pub process_name: String,
+    pub process_start_time: Option<i64>,
+    pub process_cmd: Option<String>,
+    pub condition: String,
+    pub cpu_usage: f32,
+    pub memory_bytes: i64,
+    pub peak_cpu_usage: f32,
+    pub peak_memory_bytes: i64,
+    pub actions: String,                                  // JSON con las acciones ejecutadas
+    pub started_at: chrono::NaiveDateTime,                // UTC
+    pub ended_at: Option<chrono::NaiveDateTime>,
+    pub acked_at: Option<chrono::NaiveDateTime>,
+    pub ack_note: Option<String>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = alerts)]
+pub struct NewAlert<'a> {
+    pub rule: &'a str,
+    pub pid: i32,
+    pub process_name: &'a str,
+    pub process_start_time: Option<i64>,
+    pub process_cmd: Option<&'a str>,
+    pub condition: &'a str,
+    pub cpu_usage: f32,
+    pub memory_bytes: i64,
+    pub peak_cpu_usage: f32,
+    pub peak_memory_bytes: i64,
+    pub actions: &'a str,
+    pub started_at: chrono::NaiveDateTime,
 }
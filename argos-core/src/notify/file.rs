@@ -0,0 +1,52 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::commands::types::AlertContext;
+use crate::errors::CoreResult;
+use crate::notify::Notifier;
+
+/// Añade cada alerta como una línea JSON al final del archivo.
+pub struct FileNotifier {
+    path: PathBuf,
+}
+
+impl FileNotifier {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl Notifier for FileNotifier {
+    fn notify(&self, alert: &AlertContext) -> CoreResult<()> {
+        let line = serde_json::to_string(alert)?;
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notify::test_alert;
+
+    #[test]
+    fn appends_one_json_line_per_alert() {
+        let path = std::env::temp_dir().join(format!("argos-notify-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let notifier = FileNotifier::new(path.clone());
+
+        notifier.notify(&test_alert()).unwrap();
+        notifier.notify(&test_alert()).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for line in lines {
+            let alert: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(alert["name"], "api");
+        }
+    }
+}
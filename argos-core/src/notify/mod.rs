@@ -0,0 +1,143 @@
+pub mod webhook;
+pub mod file;
+pub mod smtp;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::types::AlertContext;
+use crate::errors::{CoreError, CoreResult};
+
+/// Canal de notificación de alertas del watchdog.
+pub trait Notifier: Send {
+    fn notify(&self, alert: &AlertContext) -> CoreResult<()>;
+}
+
+fn default_retries() -> u32 {
+    3
+}
+
+fn default_timeout_secs() -> u64 {
+    5
+}
+
+fn default_smtp_port() -> u16 {
+    25
+}
+
+/// Configuración de un canal, tal como se declara en `config.toml`:
+///
+/// ```toml
+/// [notifiers.oncall]
+/// type = "webhook"
+/// url = "http://alerts.internal/hook"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum NotifierConfig {
+    Webhook {
+        url: String,
+        #[serde(default = "default_retries")]
+        retries: u32,
+        #[serde(default = "default_timeout_secs")]
+        timeout_secs: u64,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        headers: HashMap<String, String>,
+    },
+    File {
+        path: PathBuf,
+    },
+    Smtp {
+        host: String,
+        #[serde(default = "default_smtp_port")]
+        port: u16,
+        from: String,
+        to: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        username: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        password: Option<String>,
+        #[serde(default)]
+        starttls: bool,
+        #[serde(default = "default_timeout_secs")]
+        timeout_secs: u64,
+    },
+}
+
+impl NotifierConfig {
+    pub fn build(&self) -> CoreResult<Box<dyn Notifier>> {
+        Ok(match self {
+            NotifierConfig::Webhook { url, retries, timeout_secs, headers } => Box::new(
+                webhook::WebhookNotifier::new(url.clone(), *retries, *timeout_secs, headers.clone()),
+            ),
+            NotifierConfig::File { path } => Box::new(file::FileNotifier::new(path.clone())),
+            NotifierConfig::Smtp { host, port, from, to, username, password, starttls, timeout_secs } => Box::new(
+                smtp::SmtpNotifier::new(smtp::SmtpSettings {
+                    host: host.clone(),
+                    port: *port,
+                    from: from.clone(),
+                    to: to.clone(),
+                    credentials: username.clone().zip(password.clone()),
+                    starttls: *starttls,
+                    timeout_secs: *timeout_secs,
+                })?,
+            ),
+        })
+    }
+}
+
+/// Canales configurados, indexados por el nombre que usan las acciones `notify:<canal>`.
+#[derive(Default)]
+pub struct NotifierRegistry {
+    channels: HashMap<String, Box<dyn Notifier>>,
+}
+
+impl NotifierRegistry {
+    pub fn from_config(configs: &HashMap<String, NotifierConfig>) -> CoreResult<Self> {
+        let mut channels = HashMap::new();
+        for (name, config) in configs {
+            channels.insert(name.clone(), config.build()?);
+        }
+        Ok(Self { channels })
+    }
+
+    pub fn contains(&self, channel: &str) -> bool {
+        self.channels.contains_key(channel)
+    }
+
+    pub fn notify(&self, channel: &str, alert: &AlertContext) -> CoreResult<()> {
+        let notifier = self
+            .channels
+            .get(channel)
+            .ok_or_else(|| CoreError::NotifyError(format!("Canal de notificación desconocido: {}", channel)))?;
+        notifier.notify(alert)
+    }
+}
+
+/// Resultado de entregar una alerta a un canal.
+#[derive(Debug, Clone, Serialize)]
+pub struct NotifyResult {
+    pub rule: String,
+    pub pid: u32,
+    pub channel: String,
+    pub delivered: bool,
+    pub error: Option<String>,
+}
+
+/// Alerta de ejemplo para las pruebas de los canales.
+#[cfg(test)]
+pub(crate) fn test_alert() -> AlertContext {
+    AlertContext {
+        rule: "memoria".into(),
+        pid: 4242,
+        name: "api".into(),
+        condition: "mem > 1073741824".into(),
+        metric: "mem".into(),
+        value: Some("2147483648".into()),
+        cpu_usage: 12.5,
+        memory_bytes: 2_147_483_648,
+        triggered_at: chrono::Utc::now(),
+    }
+}
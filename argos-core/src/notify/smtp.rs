@@ -0,0 +1,182 @@
+use std::time::Duration;
+
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+
+use crate::commands::types::AlertContext;
+use crate::errors::{CoreError, CoreResult};
+use crate::notify::Notifier;
+
+pub struct SmtpSettings {
+    pub host: String,
+    pub port: u16,
+    pub from: String,
+    pub to: Vec<String>,
+    pub credentials: Option<(String, String)>,
+    pub starttls: bool,
+    pub timeout_secs: u64,
+}
+
+/// Envía la alerta por correo a través de un relay SMTP.
+pub struct SmtpNotifier {
+    from: Mailbox,
+    to: Vec<Mailbox>,
+    transport: SmtpTransport,
+}
+
+fn parse_mailbox(addr: &str) -> CoreResult<Mailbox> {
+    addr.parse()
+        .map_err(|e| CoreError::NotifyError(format!("Dirección de correo inválida '{}': {}", addr, e)))
+}
+
+impl SmtpNotifier {
+    pub fn new(settings: SmtpSettings) -> CoreResult<Self> {
+        if settings.to.is_empty() {
+            return Err(CoreError::NotifyError("El canal SMTP necesita al menos un destinatario".into()));
+        }
+
+        let mut builder = if settings.starttls {
+            SmtpTransport::starttls_relay(&settings.host)
+                .map_err(|e| CoreError::NotifyError(format!("Relay SMTP inválido: {}", e)))?
+        } else {
+            SmtpTransport::builder_dangerous(&settings.host)
+        };
+        builder = builder
+            .port(settings.port)
+            .timeout(Some(Duration::from_secs(settings.timeout_secs)));
+        if let Some((user, password)) = settings.credentials {
+            builder = builder.credentials(Credentials::new(user, password));
+        }
+
+        Ok(Self {
+            from: parse_mailbox(&settings.from)?,
+            to: settings.to.iter().map(|a| parse_mailbox(a)).collect::<CoreResult<_>>()?,
+            transport: builder.build(),
+        })
+    }
+}
+
+impl Notifier for SmtpNotifier {
+    fn notify(&self, alert: &AlertContext) -> CoreResult<()> {
+        let mut message = Message::builder()
+            .from(self.from.clone())
+            .subject(format!("[argos] {}: {} (PID {}) {}", alert.rule, alert.name, alert.pid, alert.condition));
+        for to in &self.to {
+            message = message.to(to.clone());
+        }
+
+        let body = format!(
+            "Regla     : {}\nProceso   : {} (PID {})\nCondición : {}\nValor     : {} = {}\nCPU       : {:.2}%\nMemoria   : {} bytes\nDisparada : {}\n",
+            alert.rule,
+            alert.name,
+            alert.pid,
+            alert.condition,
+            alert.metric,
+            alert.value.as_deref().unwrap_or("-"),
+            alert.cpu_usage,
+            alert.memory_bytes,
+            alert.triggered_at
+        );
+        let email = message
+            .body(body)
+            .map_err(|e| CoreError::NotifyError(format!("No se pudo construir el correo: {}", e)))?;
+
+        self.transport
+            .send(&email)
+            .map_err(|e| CoreError::NotifyError(format!("Error enviando correo: {}", e)))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use super::*;
+    use crate::notify::test_alert;
+
+    /// Servidor SMTP mínimo: acepta una conexión, responde a cada orden y
+    /// devuelve la conversación recibida (órdenes y mensaje).
+    fn smtp_stub() -> (u16, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut received = String::new();
+            let mut in_data = false;
+            writer.write_all(b"220 localhost ESMTP stub\r\n").unwrap();
+
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+                received.push_str(&line);
+                if in_data {
+                    if line == ".\r\n" {
+                        in_data = false;
+                        writer.write_all(b"250 OK\r\n").unwrap();
+                    }
+                    continue;
+                }
+                let command = line.to_ascii_uppercase();
+                let reply: &[u8] = if command.starts_with("EHLO") {
+                    b"250-localhost\r\n250 8BITMIME\r\n"
+                } else if command.starts_with("DATA") {
+                    in_data = true;
+                    b"354 End data with <CR><LF>.<CR><LF>\r\n"
+                } else if command.starts_with("QUIT") {
+                    writer.write_all(b"221 Bye\r\n").unwrap();
+                    break;
+                } else {
+                    b"250 OK\r\n"
+                };
+                writer.write_all(reply).unwrap();
+            }
+            received
+        });
+        (port, handle)
+    }
+
+    #[test]
+    fn sends_alert_through_relay() {
+        let (port, server) = smtp_stub();
+        let notifier = SmtpNotifier::new(SmtpSettings {
+            host: "127.0.0.1".into(),
+            port,
+            from: "argos@example.com".into(),
+            to: vec!["ops@example.com".into()],
+            credentials: None,
+            starttls: false,
+            timeout_secs: 5,
+        })
+        .unwrap();
+
+        notifier.notify(&test_alert()).unwrap();
+        drop(notifier); // cierra la conexión del pool para que el servidor termine
+
+        let received = server.join().unwrap();
+        assert!(received.contains("MAIL FROM:<argos@example.com>"), "{}", received);
+        assert!(received.contains("RCPT TO:<ops@example.com>"), "{}", received);
+        assert!(received.contains("Subject: [argos] memoria: api (PID 4242)"), "{}", received);
+    }
+
+    #[test]
+    fn requires_a_recipient() {
+        let result = SmtpNotifier::new(SmtpSettings {
+            host: "127.0.0.1".into(),
+            port: 25,
+            from: "argos@example.com".into(),
+            to: Vec::new(),
+            credentials: None,
+            starttls: false,
+            timeout_secs: 5,
+        });
+        assert!(result.is_err());
+    }
+}
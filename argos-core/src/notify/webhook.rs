@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::commands::types::AlertContext;
+use crate::errors::{CoreError, CoreResult};
+use crate::notify::Notifier;
+
+/// Envía la alerta como JSON mediante un POST HTTP, reintentando ante fallos
+/// de red y respuestas 5xx. Una respuesta 4xx no se reintenta: la petición no
+/// va a ser aceptada aunque se repita.
+pub struct WebhookNotifier {
+    url: String,
+    retries: u32,
+    headers: HashMap<String, String>,
+    agent: ureq::Agent,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: String, retries: u32, timeout_secs: u64, headers: HashMap<String, String>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(timeout_secs))
+            .build();
+        Self { url, retries, headers, agent }
+    }
+}
+
+impl Notifier for WebhookNotifier {
+    fn notify(&self, alert: &AlertContext) -> CoreResult<()> {
+        let mut last_error = String::new();
+
+        for attempt in 0..=self.retries {
+            if attempt > 0 {
+                // Backoff lineal entre reintentos
+                std::thread::sleep(Duration::from_millis(500 * attempt as u64));
+            }
+
+            let mut request = self.agent.post(&self.url);
+            for (key, value) in &self.headers {
+                request = request.set(key, value);
+            }
+
+            match request.send_json(alert) {
+                Ok(_) => return Ok(()),
+                Err(ureq::Error::Status(code, _)) if code < 500 => {
+                    let message = format!("Webhook {} rechazó la alerta (HTTP {})", self.url, code);
+                    return Err(CoreError::NotifyError(message));
+                }
+                Err(e) => last_error = e.to_string(),
+            }
+        }
+
+        Err(CoreError::NotifyError(format!(
+            "Webhook {} falló tras {} intentos: {}",
+            self.url,
+            self.retries + 1,
+            last_error
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+    use std::time::Instant;
+
+    use super::*;
+    use crate::notify::test_alert;
+
+    /// Servidor HTTP local que responde a cada petición con el siguiente código
+    /// de `statuses` y devuelve los cuerpos recibidos.
+    fn http_stub(statuses: Vec<u16>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut bodies = Vec::new();
+            for status in statuses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                bodies.push(String::from_utf8(body).unwrap());
+
+                let response = format!("HTTP/1.1 {} Stub\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            bodies
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn posts_alert_as_json() {
+        let (url, server) = http_stub(vec![200]);
+        let notifier = WebhookNotifier::new(url, 0, 5, HashMap::new());
+
+        notifier.notify(&test_alert()).unwrap();
+
+        let bodies = server.join().unwrap();
+        let sent: serde_json::Value = serde_json::from_str(&bodies[0]).unwrap();
+        assert_eq!(sent["rule"], "memoria");
+        assert_eq!(sent["pid"], 4242);
+    }
+
+    #[test]
+    fn retries_server_errors_with_backoff() {
+        let (url, server) = http_stub(vec![503, 500, 200]);
+        let notifier = WebhookNotifier::new(url, 3, 5, HashMap::new());
+
+        let start = Instant::now();
+        notifier.notify(&test_alert()).unwrap();
+
+        // Dos reintentos: 500 ms + 1000 ms de espera
+        assert!(start.elapsed() >= Duration::from_millis(1500));
+        assert_eq!(server.join().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn gives_up_after_retries() {
+        let (url, server) = http_stub(vec![502, 502]);
+        let notifier = WebhookNotifier::new(url, 1, 5, HashMap::new());
+
+        let error = notifier.notify(&test_alert()).unwrap_err().to_string();
+
+        assert!(error.contains("tras 2 intentos"), "{}", error);
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn does_not_retry_client_errors() {
+        let (url, server) = http_stub(vec![404]);
+        let notifier = WebhookNotifier::new(url, 3, 5, HashMap::new());
+
+        let error = notifier.notify(&test_alert()).unwrap_err().to_string();
+
+        assert!(error.contains("HTTP 404"), "{}", error);
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+}
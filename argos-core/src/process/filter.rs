@@ -0,0 +1,55 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::process::model::ProcessRow;
+
+/// Compara un nombre de proceso con un patrón que admite comodines `*`.
+/// Sin comodines la comparación es exacta.
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    if !pattern.contains('*') {
+        return pattern == name;
+    }
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let mut rest = name;
+
+    // El primer fragmento debe ser prefijo y el último sufijo
+    let first = parts[0];
+    if !rest.starts_with(first) {
+        return false;
+    }
+    rest = &rest[first.len()..];
+
+    let last = parts[parts.len() - 1];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// PIDs de las filas que son hilos de otra fila. Los snapshots anteriores a que
+/// se descartaran los hilos los guardan como procesos hijos del principal; se
+/// reconocen porque comparten con él nombre, comando, arranque y memoria.
+pub fn thread_row_pids(rows: &[ProcessRow]) -> HashSet<u32> {
+    let by_pid: HashMap<u32, &ProcessRow> = rows.iter().map(|r| (r.pid, r)).collect();
+    let is_thread = |row: &ProcessRow| {
+        row.parent_pid.and_then(|parent| by_pid.get(&parent)).is_some_and(|parent| {
+            parent.name == row.name
+                && parent.exe == row.exe
+                && parent.cmd == row.cmd
+                && parent.start_time == row.start_time
+                && parent.memory_mb == row.memory_mb
+                && parent.virtual_memory_mb == row.virtual_memory_mb
+        })
+    };
+    rows.iter().filter(|r| is_thread(r)).map(|r| r.pid).collect()
+}
+
+/// Quita las filas que son hilos de otra fila (ver [`thread_row_pids`]).
+pub fn drop_thread_rows(rows: Vec<ProcessRow>) -> Vec<ProcessRow> {
+    let threads = thread_row_pids(&rows);
+    rows.into_iter().filter(|r| !threads.contains(&r.pid)).collect()
+}
@@ -1,4 +1,6 @@
 pub mod model;
 pub mod fetch;
 pub mod transform;
-pub mod reader;
\ No newline at end of file
+pub mod reader;
+pub mod filter;
+pub mod procfs;
\ No newline at end of file
@@ -1,3 +1,7 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Serialize, Clone, Deserialize)]
@@ -19,14 +23,108 @@ pub struct ProcessRow {
     pub cmd: String,
 }
 
+/// Muestra de un proceso: el estado leído y cuándo se tomó. Los incrementos
+/// son respecto a la muestra anterior del mismo proceso (`None` en la primera).
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct SampleRecord {
+    pub captured_at: DateTime<Utc>,
+    pub monotonic_secs: f64, // reloj monótono del sistema (no salta con ajustes de hora)
+    pub elapsed_secs: f64,   // desde el inicio del muestreo
+    pub interval_secs: Option<f64>,
+    pub cpu_time_ms: Option<u64>, // acumulado desde que arrancó el proceso
+    pub cpu_time_delta_ms: Option<u64>,
+    pub read_bytes_delta: Option<u64>,
+    pub write_bytes_delta: Option<u64>,
+    pub process: ProcessRow,
+}
+
 #[derive(Debug, Serialize, Clone, Deserialize)]
 pub struct ProcessDelta {
     pub pid: u32,
     pub name: String,
+    #[serde(default)]
+    pub status: DeltaStatus,
     pub cpu_before: f64,
     pub cpu_after: f64,
     pub cpu_delta: f64,
     pub mem_before: f64,
     pub mem_after: f64,
     pub mem_delta: f64,
+    #[serde(default)]
+    pub instances_before: u32, // procesos agregados (0 si no estaba)
+    #[serde(default)]
+    pub instances_after: u32,
+}
+
+/// Qué le pasó a un proceso entre las dos muestras.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DeltaStatus {
+    Added,   // solo en la muestra nueva
+    Removed, // solo en la antigua
+    #[default]
+    Changed,
+    Unchanged, // variación dentro de la tolerancia
+}
+
+impl DeltaStatus {
+    /// Nombre para mostrar en tablas.
+    pub fn label(&self) -> &'static str {
+        match self {
+            DeltaStatus::Added => "nuevo",
+            DeltaStatus::Removed => "terminado",
+            DeltaStatus::Changed => "cambiado",
+            DeltaStatus::Unchanged => "igual",
+        }
+    }
+}
+
+impl fmt::Display for DeltaStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DeltaStatus::Added => "added",
+            DeltaStatus::Removed => "removed",
+            DeltaStatus::Changed => "changed",
+            DeltaStatus::Unchanged => "unchanged",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for DeltaStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "added" => Ok(DeltaStatus::Added),
+            "removed" => Ok(DeltaStatus::Removed),
+            "changed" => Ok(DeltaStatus::Changed),
+            "unchanged" => Ok(DeltaStatus::Unchanged),
+            other => Err(format!("Estado desconocido: {} (use added, removed, changed o unchanged)", other)),
+        }
+    }
+}
+
+/// Procesos de una comparación por estado.
+#[derive(Debug, Serialize, Clone, Copy, Default)]
+pub struct ComparisonSummary {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+    pub unchanged: usize,
+}
+
+impl ComparisonSummary {
+    pub fn from_deltas(deltas: &[ProcessDelta]) -> Self {
+        let mut summary = Self::default();
+        for delta in deltas {
+            match delta.status {
+                DeltaStatus::Added => summary.added += 1,
+                DeltaStatus::Removed => summary.removed += 1,
+                DeltaStatus::Changed => summary.changed += 1,
+                DeltaStatus::Unchanged => summary.unchanged += 1,
+            }
+        }
+        summary
+    }
 }
@@ -0,0 +1,86 @@
+//! Métricas que sysinfo no expone y se leen directamente de `/proc` (solo Linux).
+
+/// Descriptores de archivo abiertos por el proceso.
+#[cfg(target_os = "linux")]
+pub fn open_fd_count(pid: u32) -> Option<u64> {
+    std::fs::read_dir(format!("/proc/{}/fd", pid)).ok().map(|dir| dir.count() as u64)
+}
+
+/// Hilos del proceso (incluido el principal).
+#[cfg(target_os = "linux")]
+pub fn thread_count(pid: u32) -> Option<u64> {
+    std::fs::read_dir(format!("/proc/{}/task", pid)).ok().map(|dir| dir.count() as u64)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn open_fd_count(_pid: u32) -> Option<u64> {
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn thread_count(_pid: u32) -> Option<u64> {
+    None
+}
+
+/// Sesión del proceso (campo `session` de `stat`). Es 0 si la sesión se creó
+/// fuera del espacio de PIDs actual (p. ej. desde el anfitrión de un contenedor).
+#[cfg(target_os = "linux")]
+pub fn session_id(pid: u32) -> Option<u32> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let fields: Vec<&str> = stat.get(stat.rfind(')')? + 2..)?.split_whitespace().collect();
+    fields.get(3)?.parse().ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn session_id(_pid: u32) -> Option<u32> {
+    None
+}
+
+/// Tiempo de CPU (usuario + sistema) consumido por el proceso, en milisegundos.
+/// Se lee de `stat`, que acumula todos los hilos (también los que ya
+/// terminaron); `schedstat` solo cubre el hilo principal. La resolución es la
+/// del reloj de ticks (normalmente 10 ms).
+#[cfg(target_os = "linux")]
+pub fn cpu_time_ms(pid: u32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // El nombre (campo 2) puede contener espacios y paréntesis: se parte tras el último `)`
+    let fields: Vec<&str> = stat.get(stat.rfind(')')? + 2..)?.split_whitespace().collect();
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    (ticks > 0).then(|| (utime + stime) * 1000 / ticks as u64)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn cpu_time_ms(_pid: u32) -> Option<u64> {
+    None
+}
+
+/// Pico de memoria residente del proceso desde su último `exec` (`VmHWM`), en MB.
+#[cfg(target_os = "linux")]
+pub fn peak_rss_mb(pid: u32) -> Option<f64> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmHWM:"))?;
+    let kb: f64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb / 1024.0)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn peak_rss_mb(_pid: u32) -> Option<f64> {
+    None
+}
+
+/// Memoria proporcional (PSS) del proceso en MB: las páginas compartidas se
+/// reparten entre los procesos que las usan (`smaps_rollup`, Linux 4.14+).
+#[cfg(target_os = "linux")]
+pub fn pss_mb(pid: u32) -> Option<f64> {
+    let rollup = std::fs::read_to_string(format!("/proc/{}/smaps_rollup", pid)).ok()?;
+    let line = rollup.lines().find(|l| l.starts_with("Pss:"))?;
+    let kb: f64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb / 1024.0)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pss_mb(_pid: u32) -> Option<f64> {
+    None
+}
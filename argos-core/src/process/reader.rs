@@ -6,6 +6,12 @@ pub struct ProcessReader {
     system: sysinfo::System,
 }
 
+impl Default for ProcessReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ProcessReader {
     pub fn new() -> Self {
         let mut system = sysinfo::System::new_all();
@@ -13,17 +19,55 @@ impl ProcessReader {
         Self { system }
     }
 
+    /// Estado del sistema tras el último refresco.
+    pub fn system(&self) -> &sysinfo::System {
+        &self.system
+    }
+
     pub fn refresh(&mut self) {
         std::thread::sleep(Duration::from_millis(500));
         self.system.refresh_all();
     }
 
+    /// Refresca solo los procesos y sin espera: la CPU se calcula sobre el tiempo
+    /// transcurrido desde el refresco anterior.
+    pub fn refresh_processes(&mut self) {
+        self.system.refresh_processes();
+    }
+
+    /// Procesos según el último refresco, sin refrescar.
+    pub fn current(&self) -> Vec<ProcessRow> {
+        self.system.processes().values().map(process_to_row).collect()
+    }
+
+    /// Procesos según el último refresco, sin refrescar y sin los hilos.
+    pub fn current_processes(&self) -> Vec<ProcessRow> {
+        self.system
+            .processes()
+            .values()
+            .filter(|p| p.thread_kind().is_none())
+            .map(process_to_row)
+            .collect()
+    }
+
     pub fn get_all(&mut self) -> Vec<ProcessRow> {
         self.refresh(); 
         self.system
             .processes()
             .values()
-            .map(|p| process_to_row(p))
+            .map(process_to_row)
+            .collect()
+    }
+
+    /// Como [`get_all`](Self::get_all), pero sin los hilos que sysinfo lista
+    /// como procesos en Linux.
+    pub fn get_processes(&mut self) -> Vec<ProcessRow> {
+        self.refresh();
+        self.system
+            .processes()
+            .values()
+            .filter(|p| p.thread_kind().is_none())
+            .map(process_to_row)
             .collect()
     }
 
@@ -33,7 +77,7 @@ impl ProcessReader {
             .processes()
             .values()
             .filter(|p| pids.contains(&p.pid().as_u32()))
-            .map(|p| process_to_row(p))
+            .map(process_to_row)
             .collect()
     }
 
@@ -69,9 +113,10 @@ impl ProcessReader {
     }
 
 
+    /// Procesos zombie (sin los hilos). Tras la llamada, [`current_processes`](Self::current_processes)
+    /// devuelve el resto de procesos de la misma lectura.
     pub fn get_zombies(&mut self) -> Vec<ProcessRow> {
-        self.refresh();
-        self.get_all()
+        self.get_processes()
             .into_iter()
             .filter(|p| p.state == "Zombie") // o usa un campo booleano si lo agregas
             .collect()
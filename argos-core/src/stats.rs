@@ -0,0 +1,285 @@
+//! Estadística descriptiva e inferencial básica sobre series de muestras.
+
+pub fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Varianza muestral (divisor n − 1).
+pub fn variance(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let m = mean(values);
+    values.iter().map(|v| (v - m).powi(2)).sum::<f64>() / (values.len() - 1) as f64
+}
+
+pub fn std_dev(values: &[f64]) -> f64 {
+    variance(values).sqrt()
+}
+
+fn sorted(values: &[f64]) -> Vec<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    sorted
+}
+
+/// Percentil `p` (0–100) con interpolación lineal entre rangos.
+pub fn percentile(values: &[f64], p: f64) -> f64 {
+    percentile_sorted(&sorted(values), p)
+}
+
+fn percentile_sorted(sorted: &[f64], p: f64) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        1 => sorted[0],
+        n => {
+            let rank = (p / 100.0).clamp(0.0, 1.0) * (n - 1) as f64;
+            let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+            sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f64)
+        }
+    }
+}
+
+pub fn median(values: &[f64]) -> f64 {
+    percentile(values, 50.0)
+}
+
+/// Desviación absoluta mediana (MAD), sin escalar: mediana de `|x − mediana|`.
+/// Multiplicada por 1,4826 estima la desviación típica de datos normales.
+pub fn median_absolute_deviation(values: &[f64]) -> f64 {
+    let center = median(values);
+    let deviations: Vec<f64> = values.iter().map(|v| (v - center).abs()).collect();
+    median(&deviations)
+}
+
+/// Posiciones de los valores atípicos según las vallas de Tukey: fuera de
+/// `[Q1 − 1,5·IQR, Q3 + 1,5·IQR]`.
+pub fn tukey_outliers(values: &[f64]) -> Vec<usize> {
+    if values.len() < 4 {
+        return Vec::new();
+    }
+    let sorted = sorted(values);
+    let (q1, q3) = (percentile_sorted(&sorted, 25.0), percentile_sorted(&sorted, 75.0));
+    let fence = 1.5 * (q3 - q1);
+    values
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| **v < q1 - fence || **v > q3 + fence)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Función de distribución de la normal estándar (aproximación de Abramowitz y Stegun 7.1.26).
+pub fn normal_cdf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs() / std::f64::consts::SQRT_2);
+    let poly = t * (0.254_829_592 + t * (-0.284_496_736 + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-(x * x) / 2.0).exp();
+    if x >= 0.0 { (1.0 + erf) / 2.0 } else { (1.0 - erf) / 2.0 }
+}
+
+/// Cuantil de la normal estándar (algoritmo de Acklam, error relativo < 1,2e-9).
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [-3.969_683_028_665_376e1, 2.209_460_984_245_205e2, -2.759_285_104_469_687e2, 1.383_577_518_672_69e2, -3.066_479_806_614_716e1, 2.506_628_277_459_239];
+    const B: [f64; 5] = [-5.447_609_879_822_406e1, 1.615_858_368_580_409e2, -1.556_989_798_598_866e2, 6.680_131_188_771_972e1, -1.328_068_155_288_572e1];
+    const C: [f64; 6] = [-7.784_894_002_430_293e-3, -3.223_964_580_411_365e-1, -2.400_758_277_161_838, -2.549_732_539_343_734, 4.374_664_141_464_968, 2.938_163_982_698_783];
+    const D: [f64; 4] = [7.784_695_709_041_462e-3, 3.224_671_290_700_398e-1, 2.445_134_137_142_996, 3.754_408_661_907_416];
+
+    let p = p.clamp(1e-12, 1.0 - 1e-12);
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < 0.024_25 {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - 0.024_25 {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Cuantil de la t de Student con `df` grados de libertad (expansión de
+/// Cornish-Fisher; suficiente a partir de unos pocos grados de libertad).
+pub fn t_quantile(p: f64, df: f64) -> f64 {
+    let z = normal_quantile(p);
+    if !df.is_finite() || df <= 0.0 {
+        return z;
+    }
+    let (z3, z5) = (z.powi(3), z.powi(5));
+    z + (z3 + z) / (4.0 * df)
+        + (5.0 * z5 + 16.0 * z3 + 3.0 * z) / (96.0 * df.powi(2))
+        + (3.0 * z.powi(7) + 19.0 * z5 + 17.0 * z3 - 15.0 * z) / (384.0 * df.powi(3))
+}
+
+/// Intervalo de confianza de la diferencia de medias `b − a` (Welch).
+pub fn welch_interval(a: &[f64], b: &[f64], confidence: f64) -> (f64, f64) {
+    let diff = mean(b) - mean(a);
+    let (va, vb) = (variance(a) / a.len() as f64, variance(b) / b.len() as f64);
+    let se = (va + vb).sqrt();
+    if se == 0.0 || a.len() < 2 || b.len() < 2 {
+        return (diff, diff);
+    }
+    let df = (va + vb).powi(2) / (va.powi(2) / (a.len() - 1) as f64 + vb.powi(2) / (b.len() - 1) as f64);
+    let margin = t_quantile(1.0 - (1.0 - confidence) / 2.0, df) * se;
+    (diff - margin, diff + margin)
+}
+
+/// Intervalo de confianza bootstrap (percentiles) de `stat(b) − stat(a)`.
+/// Usa un generador determinista para que el resultado sea reproducible.
+pub fn bootstrap_interval<F>(a: &[f64], b: &[f64], confidence: f64, resamples: usize, stat: F) -> (f64, f64)
+where
+    F: Fn(&[f64]) -> f64,
+{
+    if a.is_empty() || b.is_empty() {
+        return (0.0, 0.0);
+    }
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut next = |n: usize| {
+        // xorshift64*
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        (state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 33) as usize % n
+    };
+
+    let mut diffs = Vec::with_capacity(resamples);
+    let (mut ra, mut rb) = (vec![0.0; a.len()], vec![0.0; b.len()]);
+    for _ in 0..resamples {
+        ra.iter_mut().for_each(|v| *v = a[next(a.len())]);
+        rb.iter_mut().for_each(|v| *v = b[next(b.len())]);
+        diffs.push(stat(&rb) - stat(&ra));
+    }
+    let diffs = sorted(&diffs);
+    let alpha = (1.0 - confidence) / 2.0 * 100.0;
+    (percentile_sorted(&diffs, alpha), percentile_sorted(&diffs, 100.0 - alpha))
+}
+
+/// Resultado de la prueba U de Mann-Whitney.
+#[derive(Debug, Clone, Copy)]
+pub struct MannWhitney {
+    pub u: f64,
+    pub z: f64,
+    pub p_value: f64,
+    /// Probabilidad de que un valor de `b` supere a uno de `a` (0,5 = sin diferencia).
+    pub prob_b_greater: f64,
+}
+
+/// Prueba U de Mann-Whitney bilateral con aproximación normal, corrección por
+/// empates y por continuidad. Adecuada desde unas 8 muestras por grupo.
+pub fn mann_whitney_u(a: &[f64], b: &[f64]) -> MannWhitney {
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    if a.is_empty() || b.is_empty() {
+        return MannWhitney { u: 0.0, z: 0.0, p_value: 1.0, prob_b_greater: 0.5 };
+    }
+
+    let mut all: Vec<(f64, bool)> = a.iter().map(|&v| (v, false)).chain(b.iter().map(|&v| (v, true))).collect();
+    all.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    // Rangos promedio en los empates
+    let mut rank_sum_b = 0.0;
+    let mut tie_term = 0.0;
+    let mut i = 0;
+    while i < all.len() {
+        let mut j = i;
+        while j + 1 < all.len() && all[j + 1].0 == all[i].0 {
+            j += 1;
+        }
+        let rank = (i + j) as f64 / 2.0 + 1.0;
+        let ties = (j - i + 1) as f64;
+        tie_term += ties.powi(3) - ties;
+        rank_sum_b += all[i..=j].iter().filter(|(_, in_b)| *in_b).count() as f64 * rank;
+        i = j + 1;
+    }
+
+    let u_b = rank_sum_b - n2 * (n2 + 1.0) / 2.0;
+    let n = n1 + n2;
+    let mean_u = n1 * n2 / 2.0;
+    let sigma = (n1 * n2 / 12.0 * ((n + 1.0) - tie_term / (n * (n - 1.0)))).sqrt();
+    let z = if sigma > 0.0 {
+        let diff = u_b - mean_u;
+        (diff.abs() - 0.5).max(0.0) * diff.signum() / sigma
+    } else {
+        0.0
+    };
+
+    MannWhitney {
+        u: u_b.min(n1 * n2 - u_b),
+        z,
+        p_value: (2.0 * (1.0 - normal_cdf(z.abs()))).min(1.0),
+        prob_b_greater: u_b / (n1 * n2),
+    }
+}
+
+
+/// Recta `y = intercept + slope · x` ajustada a una serie.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearFit {
+    pub slope: f64,
+    pub intercept: f64,
+    /// Proporción de la varianza de `y` que explica la recta (0–1)
+    pub r_squared: f64,
+}
+
+impl LinearFit {
+    pub fn predict(&self, x: f64) -> f64 {
+        self.intercept + self.slope * x
+    }
+}
+
+/// R² de una recta cualquiera sobre los puntos: `1 − SSres / SStot`, acotado a 0
+/// (una recta robusta puede explicar menos que la media).
+fn r_squared(x: &[f64], y: &[f64], slope: f64, intercept: f64) -> f64 {
+    let y_mean = mean(y);
+    let ss_tot: f64 = y.iter().map(|v| (v - y_mean).powi(2)).sum();
+    if ss_tot == 0.0 {
+        return 0.0;
+    }
+    let ss_res: f64 = x.iter().zip(y).map(|(x, y)| (y - intercept - slope * x).powi(2)).sum();
+    (1.0 - ss_res / ss_tot).max(0.0)
+}
+
+/// Regresión lineal por mínimos cuadrados. Con menos de dos puntos (o todos
+/// con la misma `x`) la pendiente es 0.
+pub fn linear_regression(x: &[f64], y: &[f64]) -> LinearFit {
+    let n = x.len().min(y.len());
+    let (x, y) = (&x[..n], &y[..n]);
+    let (x_mean, y_mean) = (mean(x), mean(y));
+    let sxx: f64 = x.iter().map(|v| (v - x_mean).powi(2)).sum();
+    let sxy: f64 = x.iter().zip(y).map(|(a, b)| (a - x_mean) * (b - y_mean)).sum();
+    let slope = if sxx == 0.0 { 0.0 } else { sxy / sxx };
+    let intercept = y_mean - slope * x_mean;
+    LinearFit { slope, intercept, r_squared: r_squared(x, y, slope, intercept) }
+}
+
+/// Recta de Theil-Sen: la pendiente es la mediana de las pendientes entre todos
+/// los pares de puntos, así que los picos aislados (hasta casi un 30 % de los
+/// puntos) no la mueven. Con series largas se usa un punto de cada pocos para
+/// no pasar de unos 1000 (medio millón de pares).
+pub fn theil_sen(x: &[f64], y: &[f64]) -> LinearFit {
+    const MAX_POINTS: usize = 1000;
+    let n = x.len().min(y.len());
+    let step = n.div_ceil(MAX_POINTS).max(1);
+    let points: Vec<(f64, f64)> = x[..n].iter().zip(&y[..n]).step_by(step).map(|(a, b)| (*a, *b)).collect();
+
+    let mut slopes = Vec::with_capacity(points.len() * points.len().saturating_sub(1) / 2);
+    for (i, (x1, y1)) in points.iter().enumerate() {
+        for (x2, y2) in &points[i + 1..] {
+            if x2 != x1 {
+                slopes.push((y2 - y1) / (x2 - x1));
+            }
+        }
+    }
+    if slopes.is_empty() {
+        return linear_regression(x, y);
+    }
+    let slope = median(&slopes);
+    let intercepts: Vec<f64> = x[..n].iter().zip(&y[..n]).map(|(x, y)| y - slope * x).collect();
+    let intercept = median(&intercepts);
+    LinearFit { slope, intercept, r_squared: r_squared(&x[..n], &y[..n], slope, intercept) }
+}
@@ -0,0 +1,93 @@
+use argos_core::commands::compare::ab::{AbReport, Difference, MetricComparison};
+use serde::Serialize;
+use crate::ExportError;
+
+/// Fila plana (CSV y tabla de texto): un estadístico de una métrica.
+#[derive(Serialize)]
+struct StatRow {
+    metric: String,
+    statistic: &'static str,
+    a: f64,
+    b: f64,
+    diff: f64,
+    diff_pct: Option<f64>,
+    ci_low: f64,
+    ci_high: f64,
+    p_value: f64,
+    verdict: String,
+}
+
+fn stat_rows(metric: &MetricComparison) -> Vec<StatRow> {
+    let row = |statistic, a, b, diff: &Difference| StatRow {
+        metric: metric.metric.clone(),
+        statistic,
+        a,
+        b,
+        diff: diff.value,
+        diff_pct: diff.percent,
+        ci_low: diff.ci_low,
+        ci_high: diff.ci_high,
+        p_value: metric.p_value,
+        verdict: metric.verdict.to_string(),
+    };
+    vec![
+        row("mean", metric.a.mean, metric.b.mean, &metric.mean_diff),
+        row("median", metric.a.median, metric.b.median, &metric.median_diff),
+        row("p95", metric.a.p95, metric.b.p95, &metric.p95_diff),
+    ]
+}
+
+pub fn format_ab_report(report: &AbReport, format: &str) -> Result<String, ExportError> {
+    match format {
+        "json" => crate::format_to_json(report),
+        "csv" => crate::format_to_csv(&report.metrics.iter().flat_map(stat_rows).collect::<Vec<_>>()),
+        "text" => {
+            let samples = |f: fn(&MetricComparison) -> usize| report.metrics.first().map_or(0, f);
+            let level = report.confidence * 100.0;
+            let mut output = format!(
+                "A: {} ({} muestras)\nB: {} ({} muestras)\n\n",
+                report.a,
+                samples(|m| m.a.samples),
+                report.b,
+                samples(|m| m.b.samples)
+            );
+
+            let rows: Vec<StatRow> = report.metrics.iter().flat_map(stat_rows).collect();
+            let ci_header = format!("IC {:.0} %", level);
+            output.push_str(&crate::format_to_text(
+                &rows,
+                |r: &StatRow| vec![
+                    r.metric.clone(),
+                    r.statistic.to_string(),
+                    format!("{:.2}", r.a),
+                    format!("{:.2}", r.b),
+                    format!("{:+.2}", r.diff),
+                    r.diff_pct.map_or("-".to_string(), |p| format!("{:+.1} %", p)),
+                    format!("[{:+.2}, {:+.2}]", r.ci_low, r.ci_high),
+                ],
+                &["Métrica", "Estadístico", "A", "B", "B − A", "Δ %", &ci_header]
+            ));
+
+            output.push('\n');
+            output.push_str(&crate::format_to_text(
+                &report.metrics,
+                |m: &MetricComparison| vec![
+                    m.metric.clone(),
+                    format!("{:.1}", m.mann_whitney_u),
+                    format!("{:.4}", m.p_value),
+                    format!("{:.2}", m.prob_b_greater),
+                    m.verdict.to_string(),
+                ],
+                &["Métrica", "U", "p", "P(B > A)", "Veredicto"]
+            ));
+
+            output.push_str(&format!(
+                "\nVeredicto: {} (Mann-Whitney, α = {:.2}; menor es mejor)\n",
+                report.verdict,
+                1.0 - report.confidence
+            ));
+            Ok(output)
+        }
+        _ => Err(ExportError::UnsupportedFormat(format.to_string())),
+    }
+}
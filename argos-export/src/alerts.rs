@@ -0,0 +1,88 @@
+use argos_core::models::Alert;
+use chrono::NaiveDateTime;
+use crate::ExportError;
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+fn format_time(time: Option<NaiveDateTime>) -> String {
+    time.map_or("-".to_string(), |t| t.format(TIME_FORMAT).to_string())
+}
+
+fn memory_mb(bytes: i64) -> String {
+    format!("{:.2}", bytes as f64 / (1024.0 * 1024.0))
+}
+
+pub fn format_alert_list(alerts: &[Alert], format: &str) -> Result<String, ExportError> {
+    match format {
+        "json" => crate::format_to_json(alerts),
+        "csv" => crate::format_to_csv(alerts),
+        "text" => Ok(crate::format_to_text(
+            alerts,
+            |a: &Alert| vec![
+                a.id.to_string(),
+                a.rule.clone(),
+                a.pid.to_string(),
+                a.process_name.clone(),
+                a.condition.clone(),
+                format!("{:.2}", a.peak_cpu_usage),
+                memory_mb(a.peak_memory_bytes),
+                format_time(Some(a.started_at)),
+                format_time(a.ended_at),
+                format_time(a.acked_at),
+                a.action_records().len().to_string(),
+            ],
+            &["ID", "Regla", "PID", "Proceso", "Condición", "Pico CPU %", "Pico RAM MB", "Inicio (UTC)", "Fin", "Ack", "Acciones"]
+        )),
+        _ => Err(ExportError::UnsupportedFormat(format.to_string())),
+    }
+}
+
+pub fn format_alert_detail(alert: &Alert, format: &str) -> Result<String, ExportError> {
+    match format {
+        "json" => {
+            // Las acciones se guardan como JSON; se anidan en lugar de mostrarse como texto
+            let mut value = serde_json::to_value(alert)?;
+            value["actions"] = serde_json::to_value(alert.action_records())?;
+            crate::format_to_json(&value)
+        }
+        "csv" => crate::format_to_csv(&[alert]),
+        "text" => {
+            use std::fmt::Write;
+            let mut output = String::new();
+
+            writeln!(output, "Alerta #{}", alert.id).unwrap();
+            writeln!(output, "Regla       : {}", alert.rule).unwrap();
+            writeln!(output, "Proceso     : {} (PID {})", alert.process_name, alert.pid).unwrap();
+            if let Some(cmd) = alert.process_cmd.as_deref().filter(|c| !c.is_empty()) {
+                writeln!(output, "Comando     : {}", cmd).unwrap();
+            }
+            writeln!(output, "Condición   : {}", alert.condition).unwrap();
+            writeln!(output, "CPU         : {:.2}% (pico {:.2}%)", alert.cpu_usage, alert.peak_cpu_usage).unwrap();
+            writeln!(output, "RAM         : {} MB (pico {} MB)", memory_mb(alert.memory_bytes), memory_mb(alert.peak_memory_bytes)).unwrap();
+            writeln!(output, "Inicio      : {} UTC", format_time(Some(alert.started_at))).unwrap();
+            writeln!(output, "Fin         : {}", alert.ended_at.map_or("activa".to_string(), |t| format!("{} UTC", t.format(TIME_FORMAT)))).unwrap();
+            match alert.acked_at {
+                Some(acked) => {
+                    writeln!(output, "Ack         : {} UTC", acked.format(TIME_FORMAT)).unwrap();
+                    if let Some(note) = &alert.ack_note {
+                        writeln!(output, "Nota        : {}", note).unwrap();
+                    }
+                }
+                None => writeln!(output, "Ack         : pendiente").unwrap(),
+            }
+
+            let actions = alert.action_records();
+            writeln!(output, "\nAcciones ({}):", actions.len()).unwrap();
+            for record in actions {
+                write!(output, "  {} {}", record.at.format(TIME_FORMAT), record.action).unwrap();
+                if let Some(result) = record.result {
+                    write!(output, " -> {}", result).unwrap();
+                }
+                output.push('\n');
+            }
+
+            Ok(output)
+        }
+        _ => Err(ExportError::UnsupportedFormat(format.to_string())),
+    }
+}
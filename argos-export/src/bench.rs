@@ -0,0 +1,117 @@
+use argos_core::commands::bench::{BenchDelta, BenchReport, BenchResult, MetricStats};
+use serde::Serialize;
+use crate::ExportError;
+
+/// Fila plana para CSV: un estadístico por comando y métrica.
+#[derive(Serialize)]
+struct StatRow<'a> {
+    command: &'a str,
+    runs: usize,
+    metric: &'a str,
+    mean: f64,
+    std_dev: f64,
+    median: f64,
+    min: f64,
+    max: f64,
+    outliers: String,
+}
+
+fn metric_label(metric: &str) -> &str {
+    match metric {
+        "wall_secs" => "Tiempo real (s)",
+        "cpu_secs" => "CPU (s)",
+        "peak_rss_mb" => "Pico de RSS (MB)",
+        other => other,
+    }
+}
+
+fn outlier_list(runs: &[usize], separator: &str) -> String {
+    runs.iter().map(|r| r.to_string()).collect::<Vec<_>>().join(separator)
+}
+
+fn format_result_text(result: &BenchResult) -> String {
+    let mut output = format!(
+        "Comando: {} ({} ejecuciones, {} de calentamiento)\n",
+        result.command,
+        result.runs.len(),
+        result.warmup
+    );
+    output.push_str(&crate::format_to_text(
+        &result.metrics,
+        |m: &MetricStats| vec![
+            metric_label(&m.metric).to_string(),
+            format!("{:.3}", m.mean),
+            format!("{:.3}", m.std_dev),
+            format!("{:.3}", m.median),
+            format!("{:.3}", m.min),
+            format!("{:.3}", m.max),
+            if m.outliers.is_empty() { "-".to_string() } else { outlier_list(&m.outliers, ", ") },
+        ],
+        &["Métrica", "Media", "Desv.Est", "Mediana", "Mín", "Máx", "Atípicos (ejecución)"]
+    ));
+    if result.failed_runs > 0 {
+        output.push_str(&format!(
+            "⚠ {} de {} ejecuciones terminaron con un código distinto de 0\n",
+            result.failed_runs,
+            result.runs.len()
+        ));
+    }
+    output
+}
+
+pub fn format_bench_report(report: &BenchReport, format: &str) -> Result<String, ExportError> {
+    match format {
+        "json" => crate::format_to_json(report),
+        "csv" => {
+            let mut output = String::new();
+            for delta in report.comparison.iter().flatten() {
+                output.push_str(&format!(
+                    "# compare metric={} a_mean={:.6} b_mean={:.6} ratio={} p_value={:.6}\n",
+                    delta.metric,
+                    delta.a_mean,
+                    delta.b_mean,
+                    delta.ratio.map_or(String::new(), |r| format!("{:.6}", r)),
+                    delta.p_value
+                ));
+            }
+            let rows: Vec<StatRow> = report
+                .results
+                .iter()
+                .flat_map(|result| {
+                    result.metrics.iter().map(move |m| StatRow {
+                        command: &result.command,
+                        runs: result.runs.len(),
+                        metric: &m.metric,
+                        mean: m.mean,
+                        std_dev: m.std_dev,
+                        median: m.median,
+                        min: m.min,
+                        max: m.max,
+                        outliers: outlier_list(&m.outliers, ";"),
+                    })
+                })
+                .collect();
+            output.push_str(&crate::format_to_csv(&rows)?);
+            Ok(output)
+        }
+        "text" => {
+            let mut output = report.results.iter().map(format_result_text).collect::<Vec<_>>().join("\n");
+            if let (Some(comparison), [a, b]) = (&report.comparison, report.results.as_slice()) {
+                output.push_str(&format!("\nA: {}\nB: {}\n", a.command, b.command));
+                output.push_str(&crate::format_to_text(
+                    comparison,
+                    |d: &BenchDelta| vec![
+                        metric_label(&d.metric).to_string(),
+                        format!("{:.3}", d.a_mean),
+                        format!("{:.3}", d.b_mean),
+                        d.ratio.map_or("-".to_string(), |r| format!("{:.2}×", r)),
+                        format!("{:.4}", d.p_value),
+                    ],
+                    &["Métrica", "A (media)", "B (media)", "B / A", "p (Mann-Whitney)"]
+                ));
+            }
+            Ok(output)
+        }
+        _ => Err(ExportError::UnsupportedFormat(format.to_string())),
+    }
+}
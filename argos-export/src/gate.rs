@@ -0,0 +1,101 @@
+use argos_core::commands::compare::gate::{GateReport, GateViolation, ThresholdResult};
+use crate::ExportError;
+
+fn status(passed: bool) -> &'static str {
+    if passed { "OK" } else { "FALLO" }
+}
+
+pub fn format_gate_report(report: &GateReport, format: &str) -> Result<String, ExportError> {
+    match format {
+        "json" => crate::format_to_json(report),
+        "csv" => crate::format_to_csv(&report.violations().collect::<Vec<_>>()),
+        "text" => {
+            let violations: Vec<&GateViolation> = report.violations().collect();
+            let mut output = format!(
+                "Umbrales: {} sobre {} procesos — {}\n\n",
+                report.thresholds.len(),
+                report.processes,
+                status(report.passed)
+            );
+
+            output.push_str(&crate::format_to_text(
+                &report.thresholds,
+                |t: &ThresholdResult| vec![
+                    t.threshold.clone(),
+                    t.evaluated.to_string(),
+                    t.violations.len().to_string(),
+                    status(t.violations.is_empty()).to_string(),
+                ],
+                &["Umbral", "Evaluados", "Violaciones", "Estado"]
+            ));
+
+            if !violations.is_empty() {
+                output.push('\n');
+                output.push_str(&crate::format_to_text(
+                    &violations,
+                    |v: &&GateViolation| vec![
+                        v.threshold.clone(),
+                        v.pid.to_string(),
+                        v.name.clone(),
+                        format!("{:.2}", v.value),
+                    ],
+                    &["Umbral", "PID", "Proceso", "Valor"]
+                ));
+            }
+
+            Ok(output)
+        }
+        _ => Err(ExportError::UnsupportedFormat(format.to_string())),
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Reporte JUnit XML: un caso de prueba por umbral, fallido si algún proceso lo cumple.
+pub fn format_gate_junit(report: &GateReport, suite: &str) -> String {
+    let failures = report.thresholds.iter().filter(|t| !t.violations.is_empty()).count();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"argos\" tests=\"{}\" failures=\"{}\">\n",
+        report.thresholds.len(),
+        failures
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+        escape_xml(suite),
+        report.thresholds.len(),
+        failures
+    ));
+
+    for threshold in &report.thresholds {
+        let name = escape_xml(&threshold.threshold);
+        if threshold.violations.is_empty() {
+            xml.push_str(&format!("    <testcase classname=\"{}\" name=\"{}\"/>\n", escape_xml(suite), name));
+            continue;
+        }
+
+        let details: Vec<String> = threshold
+            .violations
+            .iter()
+            .map(|v| format!("{} (PID {}): {:.2}", v.name, v.pid, v.value))
+            .collect();
+        xml.push_str(&format!("    <testcase classname=\"{}\" name=\"{}\">\n", escape_xml(suite), name));
+        xml.push_str(&format!(
+            "      <failure message=\"{} procesos cumplen {}\">{}</failure>\n",
+            threshold.violations.len(),
+            name,
+            escape_xml(&details.join("\n"))
+        ));
+        xml.push_str("    </testcase>\n");
+    }
+
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
@@ -0,0 +1,70 @@
+use argos_core::commands::leak::{LeakReport, LeakVerdict};
+use crate::ExportError;
+
+/// Informe de fugas. En CSV el resumen va como comentario y después una fila
+/// por muestra (las de calentamiento marcadas).
+pub fn format_leak_report(report: &LeakReport, format: &str) -> Result<String, ExportError> {
+    match format {
+        "json" => crate::format_to_json(report),
+        "csv" => {
+            let mut output = format!(
+                "# leak pid={} name={} metric={} verdict={} growth_mb_per_hour={:.4} r_squared={:.4} start_mb={:.2} end_mb={:.2} limit_mb={:.2} time_to_limit_secs={} duration_secs={:.1} warmup_secs={:.1} analysed_samples={} exited_early={}\n",
+                report.pid,
+                report.name,
+                report.metric.to_string().to_lowercase(),
+                match report.verdict {
+                    LeakVerdict::Leak => "leak",
+                    LeakVerdict::PossibleLeak => "possible_leak",
+                    LeakVerdict::NoLeak => "no_leak",
+                    LeakVerdict::Inconclusive => "inconclusive",
+                },
+                report.growth_mb_per_hour,
+                report.r_squared,
+                report.start_mb,
+                report.end_mb,
+                report.limit_mb,
+                report.time_to_limit_secs.map_or(String::new(), |s| format!("{:.0}", s)),
+                report.duration_secs,
+                report.warmup_secs,
+                report.analysed_samples,
+                report.exited_early
+            );
+            output.push_str(&crate::format_to_csv(&report.samples)?);
+            Ok(output)
+        }
+        "text" => {
+            let verdict = match report.verdict {
+                LeakVerdict::Leak => "🔴 FUGA",
+                LeakVerdict::PossibleLeak => "🟠 POSIBLE FUGA",
+                LeakVerdict::NoLeak => "🟢 SIN FUGA",
+                LeakVerdict::Inconclusive => "⚪ NO CONCLUYENTE",
+            };
+            let mut output = format!("Análisis de fugas: {} (PID {}) · {}\n", report.name, report.pid, report.metric);
+            output.push_str(&format!(
+                "Duración: {} (calentamiento {}) · {} muestras, {} analizadas\n",
+                crate::format_duration(report.duration_secs.round() as u64),
+                crate::format_duration(report.warmup_secs.round() as u64),
+                report.samples.len(),
+                report.analysed_samples
+            ));
+            if report.exited_early {
+                output.push_str("⚠️  El proceso terminó antes de acabar la ventana\n");
+            }
+            if report.analysed_samples > 0 {
+                output.push_str(&format!(
+                    "Tendencia: {:.2} → {:.2} MB · {:+.2} MB/h · R² {:.3}\n",
+                    report.start_mb, report.end_mb, report.growth_mb_per_hour, report.r_squared
+                ));
+                let projection = match report.time_to_limit_secs {
+                    Some(secs) if secs <= 0.0 => "ya superado".to_string(),
+                    Some(secs) => format!("alcanzado en ~{}", crate::format_duration(secs.round() as u64)),
+                    None => "no se alcanza (la memoria no crece)".to_string(),
+                };
+                output.push_str(&format!("Límite: {:.2} MB · {}\n", report.limit_mb, projection));
+            }
+            output.push_str(&format!("Veredicto: {} — {}\n", verdict, report.reason));
+            Ok(output)
+        }
+        _ => Err(ExportError::UnsupportedFormat(format.to_string())),
+    }
+}
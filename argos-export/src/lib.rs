@@ -3,15 +3,34 @@
 pub mod error;
 pub mod process;
 pub mod samples;
-use argos_core::process::model::ProcessRow;
+pub mod alerts;
+pub mod watchdog;
+pub mod series;
+pub mod gate;
+pub mod ab;
+pub mod run;
+pub mod bench;
+pub mod top;
+pub mod zombies;
+pub mod leak;
 pub use error::ExportError;
 pub use process::{format_process_list, format_process_info, format_process_tree, format_comparison};
-pub use samples::format_samples_list;
+pub use samples::{format_samples_list, format_sample_record, format_sample_run, format_live_tick};
+pub use alerts::{format_alert_list, format_alert_detail};
+pub use watchdog::format_backtest_report;
+pub use series::format_series;
+pub use gate::{format_gate_report, format_gate_junit};
+pub use ab::format_ab_report;
+pub use run::{format_run_summary, format_run_profile};
+pub use bench::format_bench_report;
+pub use top::format_top_frame;
+pub use zombies::format_zombie_report;
+pub use leak::format_leak_report;
 
 use serde::Serialize;
 
 pub fn format_to_json<T: ?Sized + Serialize>(value: &T) -> Result<String, ExportError> {
-    serde_json::to_string_pretty(value).map_err(|e| ExportError::from(e))
+    serde_json::to_string_pretty(value).map_err(ExportError::from)
 }
 
 pub fn format_to_csv<T: serde::Serialize>(data: &[T]) -> Result<String, ExportError> {
@@ -25,27 +44,28 @@ pub fn format_to_csv<T: serde::Serialize>(data: &[T]) -> Result<String, ExportEr
     Ok(String::from_utf8(data)?)
 }
 
-pub fn format_to_text<F>(
-    rows: &[ProcessRow],
+pub fn format_to_text<T, F>(
+    rows: &[T],
     row_mapper: F,
     headers: &[&str],
 ) -> String
 where
-    F: Fn(&ProcessRow) -> Vec<String>,
+    F: Fn(&T) -> Vec<String>,
 {
     // 1. Mapear filas a vectores de strings
-    let mapped_rows: Vec<Vec<String>> = rows.iter().map(|p| row_mapper(p)).collect();
+    let mapped_rows: Vec<Vec<String>> = rows.iter().map(row_mapper).collect();
 
     // 2. Calcular el ancho máximo de cada columna (incluye headers)
     let mut col_widths: Vec<usize> = headers
         .iter()
-        .map(|h| h.len())
+        .map(|h| h.chars().count())
         .collect();
 
     for row in &mapped_rows {
         for (i, cell) in row.iter().enumerate() {
-            if cell.len() > col_widths[i] {
-                col_widths[i] = cell.len();
+            let width = cell.chars().count();
+            if width > col_widths[i] {
+                col_widths[i] = width;
             }
         }
     }
@@ -75,3 +95,17 @@ where
 
     output
 }
+
+/// Duración legible con las dos unidades más significativas (`3 d 4 h`, `5 min 10 s`).
+pub(crate) fn format_duration(secs: u64) -> String {
+    let (days, hours, minutes, seconds) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60, secs % 60);
+    if days > 0 {
+        format!("{} d {} h", days, hours)
+    } else if hours > 0 {
+        format!("{} h {} min", hours, minutes)
+    } else if minutes > 0 {
+        format!("{} min {} s", minutes, seconds)
+    } else {
+        format!("{} s", seconds)
+    }
+}
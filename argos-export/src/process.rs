@@ -1,8 +1,8 @@
-use std::collections::HashMap;
-
 use crate::{error::ExportError, format_to_csv, format_to_json};
 use ansi_term::Colour;
-use argos_core::process::model::ProcessRow;
+use argos_core::commands::family::{FamilyTree, ProcessNode};
+use argos_core::process::model::{ComparisonSummary, DeltaStatus, ProcessDelta, ProcessRow};
+use serde::Serialize;
 
 
 pub fn format_process_list(rows: &[ProcessRow], format: &str) -> Result<String, ExportError> {
@@ -56,95 +56,116 @@ pub fn format_process_info(info: &ProcessRow, format: &str) -> Result<String, Ex
     }
 }
 
-pub fn format_process_tree(root: u32, rows: &[ProcessRow], format: &str) -> Result<String, ExportError> {
-    match format {
-        "json" => crate::format_to_json(rows),
-        "csv" => crate::format_to_csv(rows),
-        "text" => {
-            let mut output = String::new();
-            let mut map: HashMap<u32, Vec<&ProcessRow>> = HashMap::new();
-
-            // Agrupar hijos por padre
-            for p in rows {
-                if let Some(ppid) = p.parent_pid {
-                    map.entry(ppid).or_default().push(p);
-                }
-            }
-
-            fn print_node(
-                p: &ProcessRow,
-                map: &HashMap<u32, Vec<&ProcessRow>>,
-                indent: String,
-                is_last: bool,
-                output: &mut String,
-            ) {
-                // Símbolos de rama
-                let branch = if indent.is_empty() {
-                    String::new()
-                } else if is_last {
-                    format!("{}└─ ", indent)
-                } else {
-                    format!("{}├─ ", indent)
-                };
+fn node_line(node: &ProcessNode, target: Option<u32>) -> String {
+    let p = node.process();
+    // Color según estado
+    let paint = |name: &str| match p.state.as_str() {
+        "Zombie" => Colour::Red.paint(name.to_string()),
+        "Sleeping" | "Sleep" => Colour::Yellow.paint(name.to_string()),
+        _ => Colour::Blue.paint(name.to_string()),
+    };
+
+    let mut line = if node.count() > 1 {
+        const SHOWN_PIDS: usize = 3;
+        let mut pids: Vec<String> = node.processes.iter().take(SHOWN_PIDS).map(|r| r.pid.to_string()).collect();
+        if node.count() > SHOWN_PIDS {
+            pids.push("…".to_string());
+        }
+        format!(
+            "{}×[{}] (PIDs {}, CPU {:.2}%, MEM {:.2} MB)",
+            node.count(),
+            paint(&p.name),
+            pids.join(", "),
+            node.processes.iter().map(|r| r.cpu_usage).sum::<f64>(),
+            node.processes.iter().map(|r| r.memory_mb).sum::<f64>()
+        )
+    } else {
+        format!("{} (PID {}, CPU {:.2}%, MEM {:.2} MB)", paint(&p.name), p.pid, p.cpu_usage, p.memory_mb)
+    };
+    let totals = &node.totals;
+    if totals.descendants > 0 {
+        line.push_str(&format!(
+            " · subárbol: {} desc., CPU {:.2}%, MEM {:.2} MB, E/S {:.2}/{:.2} MB",
+            totals.descendants, totals.cpu_usage, totals.memory_mb, totals.read_disk_usage, totals.write_disk_usage
+        ));
+    }
+    if target.is_some_and(|t| node.processes.iter().any(|r| r.pid == t)) {
+        line.push_str(" ◀");
+    }
+    line
+}
 
-                // Color según estado
-                let name_colored = match p.state.as_str() {
-                    "Zombie" => Colour::Red.paint(&p.name),
-                    "Sleeping" => Colour::Yellow.paint(&p.name),
-                    _ => Colour::Blue.paint(&p.name),
-                };
+fn print_node(node: &ProcessNode, target: Option<u32>, prefix: &str, branch: &str, child_prefix: &str, output: &mut String) {
+    output.push_str(&format!("{}{}{}\n", prefix, branch, node_line(node, target)));
 
-                let line = format!(
-                    "{}{} (PID {}, CPU {:.2}%, MEM {:.2} MB)\n",
-                    branch, name_colored, p.pid, p.cpu_usage, p.memory_mb
-                );
-                output.push_str(&line);
-
-                // Procesar hijos recursivamente
-                if let Some(children) = map.get(&p.pid) {
-                    let count = children.len();
-                    for (i, child) in children.iter().enumerate() {
-                        let new_indent = if indent.is_empty() {
-                            String::new()
-                        } else if is_last {
-                            format!("{}   ", indent)
-                        } else {
-                            format!("{}│  ", indent)
-                        };
-                        print_node(child, map, new_indent, i == count - 1, output);
-                    }
-                }
-            }
+    let prefix = format!("{}{}", prefix, child_prefix);
+    let count = node.children.len() + usize::from(node.hidden > 0);
+    for (i, child) in node.children.iter().enumerate() {
+        let last = i == count - 1;
+        print_node(child, target, &prefix, if last { "└─ " } else { "├─ " }, if last { "   " } else { "│  " }, output);
+    }
+    if node.hidden > 0 {
+        let noun = if node.hidden == 1 { "proceso" } else { "procesos" };
+        output.push_str(&format!("{}└─ … {} {} más\n", prefix, node.hidden, noun));
+    }
+}
 
-            // Buscar el proceso raíz por PID
-            if let Some(root_process) = rows.iter().find(|p| p.pid == root) {
-                print_node(root_process, &map, String::new(), true, &mut output);
-            } else {
-                output.push_str(&format!("No se encontró el proceso raíz con PID {}\n", root));
+pub fn format_process_tree(tree: &FamilyTree, format: &str) -> Result<String, ExportError> {
+    match format {
+        "json" => crate::format_to_json(tree),
+        "csv" => crate::format_to_csv(&tree.rows()),
+        "text" => {
+            let mut output = String::new();
+            // Con `family --pid` el consultado es la raíz y no hace falta señalarlo
+            let target = tree.target.filter(|t| !tree.roots.iter().any(|r| r.process().pid == *t));
+            for root in &tree.roots {
+                print_node(root, target, "", "", "", &mut output);
             }
-
             Ok(output)
         }
         _ => Err(ExportError::UnsupportedFormat(format.to_string())),
     }
 }
 
+#[derive(Serialize)]
+struct ComparisonJson<'a> {
+    summary: &'a ComparisonSummary,
+    processes: &'a [ProcessDelta],
+}
+
+fn summary_line(summary: &ComparisonSummary) -> String {
+    format!(
+        "Nuevos: {}  Terminados: {}  Cambiados: {}  Sin cambios: {}",
+        summary.added, summary.removed, summary.changed, summary.unchanged
+    )
+}
+
+/// Comparación de dos muestras. `summary` lleva los conteos por estado de la
+/// comparación completa, aunque `comparison` esté filtrada.
 pub fn format_comparison(
-    comparison: &[argos_core::process::model::ProcessDelta],
+    comparison: &[ProcessDelta],
+    summary: &ComparisonSummary,
     format: &str,
 ) -> Result<String, ExportError> {
     match format {
-        "json" => format_to_json(comparison),
-        "csv" => format_to_csv(comparison),
+        "json" => format_to_json(&ComparisonJson { summary, processes: comparison }),
+        "csv" => Ok(format!(
+            "# added={},removed={},changed={},unchanged={}\n{}",
+            summary.added,
+            summary.removed,
+            summary.changed,
+            summary.unchanged,
+            format_to_csv(comparison)?
+        )),
         "text" => {
-            let mut output = String::new();
+            let mut output = format!("{}\n\n", summary_line(summary));
             output.push_str(&format!(
-                "{:<6} {:<25} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}\n",
-                "PID", "Name", "CPU Before", "CPU After", "CPU Δ",
+                "{:<6} {:<25} {:<10} {:>7} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}\n",
+                "PID", "Name", "Estado", "Inst.", "CPU Before", "CPU After", "CPU Δ",
                 "Mem Before", "Mem After", "Mem Δ"
             ));
-            output.push_str(&format!("{:-<6} {:-<25} {:-<10} {:-<10} {:-<10} {:-<10} {:-<10} {:-<10}\n",
-                "", "", "", "", "", "", "", ""
+            output.push_str(&format!("{:-<6} {:-<25} {:-<10} {:-<7} {:-<10} {:-<10} {:-<10} {:-<10} {:-<10} {:-<10}\n",
+                "", "", "", "", "", "", "", "", "", ""
             ));
 
             for delta in comparison {
@@ -160,10 +181,20 @@ pub fn format_comparison(
                     Colour::Red.paint(format!("{:.2}", delta.mem_delta))
                 };
 
+                let status = format!("{:<10}", delta.status.label());
+                let status = match delta.status {
+                    DeltaStatus::Added => Colour::Green.paint(status),
+                    DeltaStatus::Removed => Colour::Red.paint(status),
+                    DeltaStatus::Changed => Colour::Yellow.paint(status),
+                    DeltaStatus::Unchanged => Colour::White.dimmed().paint(status),
+                };
+
                 output.push_str(&format!(
-                    "{:<6} {:<25} {:>10.2} {:>10.2} {:>10} {:>10.2} {:>10.2} {:>10}\n",
+                    "{:<6} {:<25} {} {:>7} {:>10.2} {:>10.2} {:>10} {:>10.2} {:>10.2} {:>10}\n",
                     delta.pid,
                     delta.name,
+                    status,
+                    format!("{}→{}", delta.instances_before, delta.instances_after),
                     delta.cpu_before,
                     delta.cpu_after,
                     cpu_delta,
@@ -0,0 +1,90 @@
+use argos_core::commands::run::{RunProfile, RunSummary};
+use crate::ExportError;
+
+fn seconds(value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |v| format!("{:.3}", v))
+}
+
+fn exit_text(summary: &RunSummary) -> String {
+    match (summary.exit_code, summary.signal) {
+        (Some(code), _) => code.to_string(),
+        (None, Some(signal)) => format!("señal {}", signal),
+        (None, None) => "desconocido".to_string(),
+    }
+}
+
+/// Campos del resumen como pares `clave=valor`, para los comentarios CSV.
+fn summary_pairs(summary: &RunSummary) -> String {
+    format!(
+        "command={:?} exit_code={} signal={} wall_secs={:.3} user_cpu_secs={} system_cpu_secs={} peak_rss_mb={:.2} avg_cpu={:.2} read_bytes={} written_bytes={} children={} samples={}",
+        summary.command,
+        summary.exit_code.map_or(String::new(), |c| c.to_string()),
+        summary.signal.map_or(String::new(), |s| s.to_string()),
+        summary.wall_secs,
+        summary.user_cpu_secs.map_or(String::new(), |v| format!("{:.3}", v)),
+        summary.system_cpu_secs.map_or(String::new(), |v| format!("{:.3}", v)),
+        summary.peak_rss_mb,
+        summary.avg_cpu,
+        summary.read_bytes,
+        summary.written_bytes,
+        summary.children,
+        summary.samples
+    )
+}
+
+pub fn format_run_summary(summary: &RunSummary, format: &str) -> Result<String, ExportError> {
+    match format {
+        "json" => crate::format_to_json(summary),
+        "csv" => crate::format_to_csv(std::slice::from_ref(summary)),
+        "text" => {
+            let kb = |bytes: u64| bytes as f64 / 1024.0;
+            Ok(format!(
+                "Comando: {}\n\
+                 Estado de salida: {}\n\
+                 Tiempo real (s): {:.3}\n\
+                 CPU usuario (s): {}\n\
+                 CPU sistema (s): {}\n\
+                 CPU media (%): {:.1}\n\
+                 Pico de RSS (MB): {:.2}\n\
+                 Leído de disco (KB): {:.1}\n\
+                 Escrito en disco (KB): {:.1}\n\
+                 Procesos hijos: {}\n\
+                 Muestras: {}\n",
+                summary.command,
+                exit_text(summary),
+                summary.wall_secs,
+                seconds(summary.user_cpu_secs),
+                seconds(summary.system_cpu_secs),
+                summary.avg_cpu,
+                summary.peak_rss_mb,
+                kb(summary.read_bytes),
+                kb(summary.written_bytes),
+                summary.children,
+                summary.samples
+            ))
+        }
+        _ => Err(ExportError::UnsupportedFormat(format.to_string())),
+    }
+}
+
+/// Perfil completo: el resumen y la serie de muestras. En CSV el resumen va
+/// como comentario al principio.
+pub fn format_run_profile(profile: &RunProfile, format: &str) -> Result<String, ExportError> {
+    match format {
+        "json" => crate::format_to_json(profile),
+        "csv" => {
+            let mut output = format!("# summary {}\n", summary_pairs(&profile.summary));
+            output.push_str(&crate::format_samples_list(&profile.samples, format)?);
+            Ok(output)
+        }
+        "text" => {
+            let mut output = format_run_summary(&profile.summary, format)?;
+            if !profile.samples.is_empty() {
+                output.push('\n');
+                output.push_str(&crate::format_samples_list(&profile.samples, format)?);
+            }
+            Ok(output)
+        }
+        _ => Err(ExportError::UnsupportedFormat(format.to_string())),
+    }
+}
@@ -1,64 +1,118 @@
-use argos_core::process::model::ProcessRow;
+use argos_core::commands::live::LiveTick;
+use argos_core::commands::sampling::{ProcessExit, SampleRun};
+use argos_core::process::model::SampleRecord;
+use serde::Serialize;
 use crate::ExportError;
 
-pub fn format_samples_list(samples: &[ProcessRow], format: &str) -> Result<String, ExportError> {
+const CAPTURE_FORMAT: &str = "%H:%M:%S%.3f";
+
+/// Fila plana de una muestra para CSV: los tiempos de captura y los
+/// incrementos seguidos de todos los campos del proceso.
+#[derive(Serialize)]
+struct SampleCsvRow<'a> {
+    captured_at: String,
+    monotonic_secs: f64,
+    elapsed_secs: f64,
+    interval_secs: Option<f64>,
+    cpu_time_ms: Option<u64>,
+    cpu_time_delta_ms: Option<u64>,
+    read_bytes_delta: Option<u64>,
+    write_bytes_delta: Option<u64>,
+    pid: u32,
+    name: &'a str,
+    cpu_usage: f64,
+    memory_mb: f64,
+    user: &'a str,
+    groups: &'a str,
+    state: &'a str,
+    start_time: u64,
+    start_time_human: &'a str,
+    parent_pid: Option<u32>,
+    virtual_memory_mb: f64,
+    read_disk_usage: f64,
+    write_disk_usage: f64,
+    exe: &'a str,
+    cmd: &'a str,
+}
+
+impl<'a> From<&'a SampleRecord> for SampleCsvRow<'a> {
+    fn from(s: &'a SampleRecord) -> Self {
+        let p = &s.process;
+        Self {
+            captured_at: s.captured_at.to_rfc3339(),
+            monotonic_secs: s.monotonic_secs,
+            elapsed_secs: s.elapsed_secs,
+            interval_secs: s.interval_secs,
+            cpu_time_ms: s.cpu_time_ms,
+            cpu_time_delta_ms: s.cpu_time_delta_ms,
+            read_bytes_delta: s.read_bytes_delta,
+            write_bytes_delta: s.write_bytes_delta,
+            pid: p.pid,
+            name: &p.name,
+            cpu_usage: p.cpu_usage,
+            memory_mb: p.memory_mb,
+            user: &p.user,
+            groups: &p.groups,
+            state: &p.state,
+            start_time: p.start_time,
+            start_time_human: &p.start_time_human,
+            parent_pid: p.parent_pid,
+            virtual_memory_mb: p.virtual_memory_mb,
+            read_disk_usage: p.read_disk_usage,
+            write_disk_usage: p.write_disk_usage,
+            exe: &p.exe,
+            cmd: &p.cmd,
+        }
+    }
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or("-".to_string(), |v| v.to_string())
+}
+
+fn kb(bytes: Option<u64>) -> String {
+    bytes.map_or("-".to_string(), |b| format!("{:.1}", b as f64 / 1024.0))
+}
+
+/// Columnas de texto de una muestra, en el orden de [`SAMPLE_HEADERS`].
+fn sample_columns(s: &SampleRecord) -> Vec<String> {
+    vec![
+        s.captured_at.with_timezone(&chrono::Local).format(CAPTURE_FORMAT).to_string(),
+        format!("{:.3}", s.elapsed_secs),
+        s.process.pid.to_string(),
+        s.process.name.clone(),
+        format!("{:.2}", s.process.cpu_usage),
+        optional(s.cpu_time_delta_ms),
+        format!("{:.2}", s.process.memory_mb),
+        kb(s.read_bytes_delta),
+        kb(s.write_bytes_delta),
+        s.process.state.clone(),
+    ]
+}
+
+const SAMPLE_HEADERS: [&str; 10] =
+    ["Capturado", "t (s)", "PID", "Nombre", "CPU %", "CPU ms Δ", "RAM MB", "Lectura KB Δ", "Escritura KB Δ", "Estado"];
+
+pub fn format_samples_list(samples: &[SampleRecord], format: &str) -> Result<String, ExportError> {
     match format {
         "json" => crate::format_to_json(samples),
-        "csv" => crate::format_to_csv(samples),
+        "csv" => crate::format_to_csv(&samples.iter().map(SampleCsvRow::from).collect::<Vec<_>>()),
         "text" => {
             use std::fmt::Write;
-            let mut output = String::new();
+            let mut output = crate::format_to_text(samples, sample_columns, &SAMPLE_HEADERS);
 
-            // Column widths calculadas dinámicamente
-            let ts_width = "Timestamp".len().max(samples.iter().map(|s| s.start_time_human.len()).max().unwrap_or(0));
-            let pid_width = "PID".len().max(samples.iter().map(|s| s.pid.to_string().len()).max().unwrap_or(0));
-            let name_width = "Nombre".len().max(samples.iter().map(|s| s.name.len()).max().unwrap_or(0));
-            let cpu_width = "CPU %".len().max(samples.iter().map(|s| format!("{:.2}", s.cpu_usage).len()).max().unwrap_or(0));
-            let mem_width = "RAM MB".len().max(samples.iter().map(|s| format!("{:.2}", s.memory_mb).len()).max().unwrap_or(0));
-            let state_width = "Estado".len().max(samples.iter().map(|s| s.state.len()).max().unwrap_or(0));
-
-            // Header
-            let ts_header = format!("{:<width$}", "Timestamp", width = ts_width);
-            let pid_header = format!("{:>width$}", "PID", width = pid_width);
-            let name_header = format!("{:<width$}", "Nombre", width = name_width);
-            let cpu_header = format!("{:>width$}", "CPU %", width = cpu_width);
-            let mem_header = format!("{:>width$}", "RAM MB", width = mem_width);
-            let state_header = format!("{:<width$}", "Estado", width = state_width);
-
-        writeln!(
-            &mut output,
-            "{} {} {} {} {} {}",
-            ts_header, pid_header, name_header, cpu_header, mem_header, state_header
-        ).unwrap();
-
-
-            writeln!(
-                &mut output,
-                "{:-<ts$} {:-<pid$} {:-<name$} {:-<cpu$} {:-<mem$} {:-<state$}",
-                "", "", "", "", "", "",
-                ts = ts_width, pid = pid_width, name = name_width, cpu = cpu_width, mem = mem_width, state = state_width
-            ).unwrap();
-
-            // Rows
+            // Estadísticas (por proceso si se muestrearon varios)
+            let mut pids: Vec<u32> = Vec::new();
             for s in samples {
-                let ts_col = format!("{:<width$}", s.start_time_human, width = ts_width);
-                let pid_col = format!("{:>width$}", s.pid, width = pid_width);
-                let name_col = format!("{:<width$}", s.name, width = name_width);
-                let cpu_col = format!("{:>width$.2}", s.cpu_usage, width = cpu_width);
-                let mem_col = format!("{:>width$.2}", s.memory_mb, width = mem_width);
-                let state_col = format!("{:<width$}", s.state, width = state_width);
-
-                writeln!(
-                    &mut output,
-                    "{} {} {} {} {} {}",
-                    ts_col, pid_col, name_col, cpu_col, mem_col, state_col
-                ).unwrap();
+                if !pids.contains(&s.process.pid) {
+                    pids.push(s.process.pid);
+                }
             }
-
-            // Estadísticas
-            if !samples.is_empty() {
-                let cpu_values: Vec<f64> = samples.iter().map(|s| s.cpu_usage as f64).collect();
-                let mem_values: Vec<f64> = samples.iter().map(|s| s.memory_mb as f64).collect();
+            for pid in &pids {
+                let series: Vec<&argos_core::process::model::ProcessRow> =
+                    samples.iter().map(|s| &s.process).filter(|s| s.pid == *pid).collect();
+                let cpu_values: Vec<f64> = series.iter().map(|s| s.cpu_usage).collect();
+                let mem_values: Vec<f64> = series.iter().map(|s| s.memory_mb).collect();
 
                 let avg = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
                 let std = |v: &[f64], mean: f64| (v.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / v.len() as f64).sqrt();
@@ -73,7 +127,11 @@ pub fn format_samples_list(samples: &[ProcessRow], format: &str) -> Result<Strin
                 let mem_min = mem_values.iter().cloned().fold(f64::INFINITY, f64::min);
                 let mem_max = mem_values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
 
-                writeln!(&mut output, "\nEstadísticas:").unwrap();
+                if pids.len() == 1 {
+                    writeln!(&mut output, "\nEstadísticas:").unwrap();
+                } else {
+                    writeln!(&mut output, "\nEstadísticas de {} (PID {}, {} muestras):", series[0].name, pid, series.len()).unwrap();
+                }
                 writeln!(&mut output, "CPU % -> Promedio: {:.2}, Min: {:.2}, Max: {:.2}, Desv.Est: {:.2}", cpu_avg, cpu_min, cpu_max, cpu_std).unwrap();
                 writeln!(&mut output, "RAM MB -> Promedio: {:.2}, Min: {:.2}, Max: {:.2}, Desv.Est: {:.2}", mem_avg, mem_min, mem_max, mem_std).unwrap();
             }
@@ -82,4 +140,136 @@ pub fn format_samples_list(samples: &[ProcessRow], format: &str) -> Result<Strin
         }
         _ => Err(ExportError::UnsupportedFormat(format.to_string())),
     }
-}
\ No newline at end of file
+}
+
+/// Muestreo completo: las muestras más los procesos que terminaron durante él.
+/// En CSV los procesos terminados van como comentarios al principio.
+pub fn format_sample_run(run: &SampleRun, format: &str) -> Result<String, ExportError> {
+    const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
+    match format {
+        "json" => crate::format_to_json(run),
+        "csv" => {
+            let mut output = String::new();
+            if let Some(schedule) = &run.schedule {
+                output.push_str(&format!(
+                    "# schedule interval_ms={} mean_interval_ms={:.3} ticks={} missed_ticks={} jitter_mean_ms={:.3} jitter_p95_ms={:.3} jitter_max_ms={:.3}\n",
+                    schedule.interval_ms,
+                    schedule.mean_interval_ms,
+                    schedule.ticks,
+                    schedule.missed_ticks,
+                    schedule.jitter_mean_ms,
+                    schedule.jitter_p95_ms,
+                    schedule.jitter_max_ms
+                ));
+            }
+            for exit in &run.exited {
+                output.push_str(&format!(
+                    "# exited pid={} name={} last_seen={} exited_at={}\n",
+                    exit.pid,
+                    exit.name,
+                    exit.last_seen.to_rfc3339(),
+                    exit.exited_at.to_rfc3339()
+                ));
+            }
+            output.push_str(&format_samples_list(&run.samples, format)?);
+            Ok(output)
+        }
+        "text" => {
+            let mut output = format_samples_list(&run.samples, format)?;
+            if let Some(schedule) = &run.schedule {
+                output.push_str(&format!(
+                    "\nIntervalo: pedido {:.0} ms, real {:.2} ms de media; retraso medio {:.2} ms (p95 {:.2}, máx. {:.2}); iteraciones perdidas: {}\n",
+                    schedule.interval_ms,
+                    schedule.mean_interval_ms,
+                    schedule.jitter_mean_ms,
+                    schedule.jitter_p95_ms,
+                    schedule.jitter_max_ms,
+                    schedule.missed_ticks
+                ));
+            }
+            if !run.exited.is_empty() {
+                output.push_str("\nProcesos que terminaron durante el muestreo:\n");
+                output.push_str(&crate::format_to_text(
+                    &run.exited,
+                    |e: &ProcessExit| vec![
+                        e.pid.to_string(),
+                        e.name.clone(),
+                        e.samples.to_string(),
+                        e.last_seen.with_timezone(&chrono::Local).format(TIME_FORMAT).to_string(),
+                        e.exited_at.with_timezone(&chrono::Local).format(TIME_FORMAT).to_string(),
+                    ],
+                    &["PID", "Nombre", "Muestras", "Visto por última vez", "Terminado antes de"]
+                ));
+            }
+            Ok(output)
+        }
+        _ => Err(ExportError::UnsupportedFormat(format.to_string())),
+    }
+}
+
+/// Una muestra suelta, para salida continua (`live`): una línea de tabla, JSON
+/// compacto en una línea o una fila CSV. Con `header` se antepone la cabecera
+/// (texto y CSV).
+pub fn format_sample_record(record: &SampleRecord, format: &str, header: bool) -> Result<String, ExportError> {
+    match format {
+        "json" => Ok(serde_json::to_string(record)?),
+        "csv" => {
+            let mut wtr = csv::WriterBuilder::new().has_headers(header).from_writer(vec![]);
+            wtr.serialize(SampleCsvRow::from(record))?;
+            let data = wtr.into_inner().map_err(|e| ExportError::Io(e.into_error()))?;
+            Ok(String::from_utf8(data)?.trim_end().to_string())
+        }
+        "text" => {
+            // Anchos fijos: cada línea se imprime por separado y debe quedar alineada
+            const WIDTHS: [usize; 10] = [12, 9, 7, 16, 7, 8, 9, 12, 14, 8];
+            let line = |cells: Vec<String>| {
+                cells
+                    .iter()
+                    .zip(WIDTHS)
+                    .map(|(c, w)| format!("{:<w$}", c, w = w))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .trim_end()
+                    .to_string()
+            };
+            let row = line(sample_columns(record));
+            Ok(if header {
+                format!("{}\n{}", line(SAMPLE_HEADERS.iter().map(|h| h.to_string()).collect()), row)
+            } else {
+                row
+            })
+        }
+        _ => Err(ExportError::UnsupportedFormat(format.to_string())),
+    }
+}
+
+/// Una iteración de `live`: en texto un bloque con una fila por proceso y los
+/// que terminaron marcados debajo; en JSON una línea compacta; en CSV una fila
+/// por proceso (cabecera con `header`) y los terminados como comentarios.
+pub fn format_live_tick(tick: &LiveTick, format: &str, header: bool) -> Result<String, ExportError> {
+    match format {
+        "json" => Ok(serde_json::to_string(tick)?),
+        "csv" => {
+            let mut lines = Vec::new();
+            for (i, record) in tick.samples.iter().enumerate() {
+                lines.push(format_sample_record(record, format, header && i == 0)?);
+            }
+            for exit in &tick.exited {
+                lines.push(format!("# exited pid={} name={} exited_at={}", exit.pid, exit.name, exit.exited_at.to_rfc3339()));
+            }
+            Ok(lines.join("\n"))
+        }
+        "text" => {
+            let time = tick.captured_at.with_timezone(&chrono::Local).format(CAPTURE_FORMAT);
+            let mut output = format!("── {} ── {} procesos\n", time, tick.samples.len());
+            if !tick.samples.is_empty() {
+                output.push_str(&crate::format_to_text(&tick.samples, sample_columns, &SAMPLE_HEADERS));
+            }
+            for exit in &tick.exited {
+                output.push_str(&format!("✖ terminó: {} (PID {})\n", exit.name, exit.pid));
+            }
+            Ok(output)
+        }
+        _ => Err(ExportError::UnsupportedFormat(format.to_string())),
+    }
+}
@@ -0,0 +1,119 @@
+use argos_core::commands::compare::series::{ProcessTrajectory, SeriesPoint, SnapshotSeries};
+use serde::Serialize;
+use crate::ExportError;
+
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Sparkline escalada entre el mínimo y el máximo de la serie. Los snapshots
+/// en los que el proceso no aparece se muestran como espacio; variaciones por
+/// debajo del 0,1 % del máximo se consideran ruido y se dibujan planas.
+pub fn sparkline(values: &[Option<f64>]) -> String {
+    let present = values.iter().flatten();
+    let min = present.clone().copied().fold(f64::INFINITY, f64::min);
+    let max = present.copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    values
+        .iter()
+        .map(|v| match v {
+            None => ' ',
+            Some(_) if range <= f64::EPSILON || range <= max.abs() * 0.001 => SPARK_CHARS[0],
+            Some(v) => SPARK_CHARS[(((v - min) / range) * 7.0).round() as usize],
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+struct SummaryRow {
+    pid: u32,
+    name: String,
+    present_in: usize,
+    instances_first: u32,
+    instances_last: u32,
+    cpu_first: f64,
+    cpu_last: f64,
+    cpu_peak: f64,
+    cpu_net_change: f64,
+    mem_first: f64,
+    mem_last: f64,
+    mem_peak: f64,
+    mem_net_change: f64,
+}
+
+#[derive(Serialize)]
+struct SeriesJson<'a> {
+    snapshots: &'a [String],
+    summary: Vec<SummaryRow>,
+    points: Vec<SeriesPoint>,
+}
+
+fn first_instances(p: &ProcessTrajectory) -> u32 {
+    p.instances.iter().copied().find(|&n| n > 0).unwrap_or(0)
+}
+
+fn last_instances(p: &ProcessTrajectory) -> u32 {
+    p.instances.iter().copied().rfind(|&n| n > 0).unwrap_or(0)
+}
+
+fn signed(value: f64) -> String {
+    format!("{:+.2}", value)
+}
+
+pub fn format_series(series: &SnapshotSeries, format: &str) -> Result<String, ExportError> {
+    match format {
+        "json" => crate::format_to_json(&SeriesJson {
+            snapshots: &series.snapshots,
+            summary: series
+                .processes
+                .iter()
+                .map(|p| SummaryRow {
+                    pid: p.pid,
+                    name: p.name.clone(),
+                    present_in: p.present_in,
+                    instances_first: first_instances(p),
+                    instances_last: last_instances(p),
+                    cpu_first: p.cpu_summary.first,
+                    cpu_last: p.cpu_summary.last,
+                    cpu_peak: p.cpu_summary.peak,
+                    cpu_net_change: p.cpu_summary.net_change,
+                    mem_first: p.memory_summary.first,
+                    mem_last: p.memory_summary.last,
+                    mem_peak: p.memory_summary.peak,
+                    mem_net_change: p.memory_summary.net_change,
+                })
+                .collect(),
+            points: series.points(),
+        }),
+        "csv" => crate::format_to_csv(&series.points()),
+        "text" => {
+            let mut output = format!(
+                "Serie de {} snapshots: {}\n\n",
+                series.snapshots.len(),
+                series.snapshots.join(" → ")
+            );
+            output.push_str(&crate::format_to_text(
+                &series.processes,
+                |p: &ProcessTrajectory| vec![
+                    p.pid.to_string(),
+                    p.name.clone(),
+                    format!("{}/{}", p.present_in, series.snapshots.len()),
+                    format!("{}→{}", first_instances(p), last_instances(p)),
+                    sparkline(&p.cpu),
+                    format!("{:.2}", p.cpu_summary.first),
+                    format!("{:.2}", p.cpu_summary.last),
+                    format!("{:.2}", p.cpu_summary.peak),
+                    signed(p.cpu_summary.net_change),
+                    sparkline(&p.memory),
+                    format!("{:.2}", p.memory_summary.first),
+                    format!("{:.2}", p.memory_summary.last),
+                    format!("{:.2}", p.memory_summary.peak),
+                    signed(p.memory_summary.net_change),
+                ],
+                &["PID", "Nombre", "Presente", "Inst.", "CPU", "CPU ini", "CPU fin", "CPU pico", "CPU Δ",
+                  "RAM MB", "RAM ini", "RAM fin", "RAM pico", "RAM Δ"]
+            ));
+            Ok(output)
+        }
+        _ => Err(ExportError::UnsupportedFormat(format.to_string())),
+    }
+}
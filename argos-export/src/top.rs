@@ -0,0 +1,103 @@
+use argos_core::commands::top::TopFrame;
+use argos_core::process::model::ProcessRow;
+use serde::Serialize;
+use crate::ExportError;
+
+/// Fila CSV de `top`: la iteración y la posición seguidas del proceso.
+#[derive(Serialize)]
+struct TopCsvRow<'a> {
+    iteration: usize,
+    captured_at: String,
+    rank: usize,
+    pid: u32,
+    name: &'a str,
+    user: &'a str,
+    cpu_usage: f64,
+    memory_mb: f64,
+    virtual_memory_mb: f64,
+    read_disk_usage: f64,
+    write_disk_usage: f64,
+    state: &'a str,
+    parent_pid: Option<u32>,
+    cmd: &'a str,
+}
+
+/// Comando recortado para que la tabla quepa en una línea de terminal.
+fn short_cmd(row: &ProcessRow) -> String {
+    const MAX: usize = 60;
+    let cmd = if row.cmd.is_empty() { &row.name } else { &row.cmd };
+    if cmd.chars().count() > MAX {
+        format!("{}…", cmd.chars().take(MAX - 1).collect::<String>())
+    } else {
+        cmd.clone()
+    }
+}
+
+/// Una iteración de `top`: en texto una cabecera con la hora y los totales y la
+/// tabla de procesos; en JSON una línea compacta; en CSV una fila por proceso
+/// (cabecera con `header`).
+pub fn format_top_frame(frame: &TopFrame, format: &str, header: bool) -> Result<String, ExportError> {
+    match format {
+        "json" => Ok(serde_json::to_string(frame)?),
+        "csv" => {
+            let mut wtr = csv::WriterBuilder::new().has_headers(header).from_writer(vec![]);
+            let captured_at = frame.captured_at.to_rfc3339();
+            for (i, p) in frame.processes.iter().enumerate() {
+                wtr.serialize(TopCsvRow {
+                    iteration: frame.iteration,
+                    captured_at: captured_at.clone(),
+                    rank: i + 1,
+                    pid: p.pid,
+                    name: &p.name,
+                    user: &p.user,
+                    cpu_usage: p.cpu_usage,
+                    memory_mb: p.memory_mb,
+                    virtual_memory_mb: p.virtual_memory_mb,
+                    read_disk_usage: p.read_disk_usage,
+                    write_disk_usage: p.write_disk_usage,
+                    state: &p.state,
+                    parent_pid: p.parent_pid,
+                    cmd: &p.cmd,
+                })?;
+            }
+            let data = wtr.into_inner().map_err(|e| ExportError::Io(e.into_error()))?;
+            Ok(String::from_utf8(data)?.trim_end().to_string())
+        }
+        "text" => {
+            let time = frame.captured_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S");
+            let mut output = format!(
+                "── {} ── iteración {} · {} procesos, CPU {:.1}%, RAM {:.1} MB · orden: {}\n",
+                time, frame.iteration, frame.total_processes, frame.total_cpu, frame.total_memory_mb, frame.sort_by
+            );
+            if !frame.filters.is_empty() {
+                output.push_str(&format!(
+                    "Filtros: {} ({} de {} procesos)\n",
+                    frame.filters.join(", "),
+                    frame.matched,
+                    frame.total_processes
+                ));
+            }
+            if frame.processes.is_empty() {
+                output.push_str("Ningún proceso cumple los filtros\n");
+                return Ok(output);
+            }
+            output.push_str(&crate::format_to_text(
+                &frame.processes,
+                |p: &ProcessRow| vec![
+                    p.pid.to_string(),
+                    p.user.clone(),
+                    format!("{:.2}", p.cpu_usage),
+                    format!("{:.2}", p.memory_mb),
+                    format!("{:.2}", p.virtual_memory_mb),
+                    format!("{:.2}", p.read_disk_usage),
+                    format!("{:.2}", p.write_disk_usage),
+                    p.state.clone(),
+                    short_cmd(p),
+                ],
+                &["PID", "Usuario", "CPU %", "RAM MB", "VMEM", "Lectura", "Escritura", "Estado", "Comando"],
+            ));
+            Ok(output)
+        }
+        _ => Err(ExportError::UnsupportedFormat(format.to_string())),
+    }
+}
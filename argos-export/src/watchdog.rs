@@ -0,0 +1,61 @@
+use argos_core::commands::watchdog::backtest::{BacktestFiring, BacktestReport, BacktestRuleSummary};
+use chrono::{DateTime, Utc};
+use crate::ExportError;
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+fn format_time(time: Option<DateTime<Utc>>) -> String {
+    time.map_or("-".to_string(), |t| t.format(TIME_FORMAT).to_string())
+}
+
+pub fn format_backtest_report(report: &BacktestReport, format: &str) -> Result<String, ExportError> {
+    match format {
+        "json" => crate::format_to_json(report),
+        "csv" => crate::format_to_csv(&report.firings),
+        "text" => {
+            let mut output = format!(
+                "Backtest: {} muestras entre {} y {} (UTC). No se ejecutó ninguna acción.\n\n",
+                report.samples,
+                format_time(report.from),
+                format_time(report.to)
+            );
+
+            output.push_str(&crate::format_to_text(
+                &report.rules,
+                |r: &BacktestRuleSummary| vec![
+                    r.rule.clone(),
+                    r.firings.to_string(),
+                    r.incidents.to_string(),
+                    r.processes.to_string(),
+                    format_time(r.first_fired),
+                    format_time(r.last_fired),
+                ],
+                &["Regla", "Disparos", "Incidentes", "Procesos", "Primero", "Último"]
+            ));
+
+            if report.firings.is_empty() {
+                output.push_str("\nNinguna regla se habría disparado.\n");
+            } else {
+                output.push('\n');
+                output.push_str(&crate::format_to_text(
+                    &report.firings,
+                    |f: &BacktestFiring| vec![
+                        format_time(Some(f.timestamp)),
+                        f.rule.clone(),
+                        f.pid.to_string(),
+                        f.name.clone(),
+                        f.condition.clone(),
+                        format!("{:.2}", f.cpu_usage),
+                        format!("{:.2}", f.memory_bytes as f64 / 1_048_576.0),
+                        if f.new_incident { "sí" } else { "" }.to_string(),
+                        f.actions.clone(),
+                    ],
+                    &["Instante (UTC)", "Regla", "PID", "Proceso", "Condición", "CPU %", "RAM MB", "Nuevo", "Acciones"]
+                ));
+            }
+
+            Ok(output)
+        }
+        _ => Err(ExportError::UnsupportedFormat(format.to_string())),
+    }
+}
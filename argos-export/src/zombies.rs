@@ -0,0 +1,141 @@
+use argos_core::commands::zombies::{Adopter, OrphanProcess, ZombieProcess, ZombieReport};
+use serde::Serialize;
+use crate::ExportError;
+
+/// Fila CSV común a zombies y huérfanos: `kind` distingue unos de otros y
+/// `parent_*` es el padre del zombie o quien adoptó al huérfano.
+#[derive(Serialize)]
+struct ZombieCsvRow<'a> {
+    kind: &'static str,
+    pid: u32,
+    name: &'a str,
+    state: &'a str,
+    user: &'a str,
+    cmd: &'a str,
+    age_secs: u64,
+    parent_pid: Option<u32>,
+    parent_name: Option<&'a str>,
+    parent_cmd: Option<&'a str>,
+    adopter: Option<Adopter>,
+    session_id: Option<u32>,
+    suggestion: &'a str,
+}
+
+fn csv_rows(report: &ZombieReport) -> Vec<ZombieCsvRow<'_>> {
+    let mut rows = Vec::new();
+    for group in &report.groups {
+        for z in &group.zombies {
+            rows.push(ZombieCsvRow {
+                kind: "zombie",
+                pid: z.process.pid,
+                name: &z.process.name,
+                state: &z.process.state,
+                user: &z.process.user,
+                cmd: &z.process.cmd,
+                age_secs: z.age_secs,
+                parent_pid: group.parent_pid,
+                parent_name: group.parent.as_ref().map(|p| p.name.as_str()),
+                parent_cmd: group.parent.as_ref().map(|p| p.cmd.as_str()),
+                adopter: None,
+                session_id: None,
+                suggestion: &group.suggestion,
+            });
+        }
+    }
+    for o in &report.orphans {
+        rows.push(ZombieCsvRow {
+            kind: "orphan",
+            pid: o.process.pid,
+            name: &o.process.name,
+            state: &o.process.state,
+            user: &o.process.user,
+            cmd: &o.process.cmd,
+            age_secs: o.age_secs,
+            parent_pid: Some(o.adopter_pid),
+            parent_name: Some(&o.adopter_name),
+            parent_cmd: None,
+            adopter: Some(o.adopter),
+            session_id: Some(o.session_id),
+            suggestion: &o.suggestion,
+        });
+    }
+    rows
+}
+
+pub fn format_zombie_report(report: &ZombieReport, format: &str) -> Result<String, ExportError> {
+    match format {
+        "json" => crate::format_to_json(report),
+        "csv" => crate::format_to_csv(&csv_rows(report)),
+        "text" => {
+            let mut output = String::new();
+            if report.groups.is_empty() {
+                output.push_str("No hay procesos zombie\n");
+            } else {
+                output.push_str(&format!(
+                    "Zombies: {} (padres que no los recogen: {})\n",
+                    report.zombie_count,
+                    report.groups.len()
+                ));
+            }
+            for group in &report.groups {
+                let parent = match (&group.parent, group.parent_pid) {
+                    (Some(p), _) => format!("{} (PID {}) · {}", p.name, p.pid, if p.cmd.is_empty() { "-" } else { &p.cmd }),
+                    (None, Some(pid)) => format!("PID {} (ya no existe)", pid),
+                    (None, None) => "sin padre".to_string(),
+                };
+                output.push_str(&format!(
+                    "\n▸ Padre: {}\n  {} zombies, el más antiguo arrancó hace {}\n",
+                    parent,
+                    group.zombies.len(),
+                    crate::format_duration(group.oldest_age_secs)
+                ));
+                output.push_str(&crate::format_to_text(
+                    &group.zombies,
+                    |z: &ZombieProcess| vec![
+                        z.process.pid.to_string(),
+                        z.process.name.clone(),
+                        z.process.user.clone(),
+                        crate::format_duration(z.age_secs),
+                    ],
+                    &["PID", "Nombre", "Usuario", "Edad (máx.)"],
+                ));
+                output.push_str(&format!("  → {}\n", group.suggestion));
+            }
+
+            if report.orphans.is_empty() {
+                output.push_str("\nNo se detectaron huérfanos adoptados\n");
+            } else {
+                output.push_str(&format!("\nHuérfanos adoptados: {}\n", report.orphans.len()));
+                output.push_str(&crate::format_to_text(
+                    &report.orphans,
+                    |o: &OrphanProcess| vec![
+                        o.process.pid.to_string(),
+                        o.process.name.clone(),
+                        o.process.user.clone(),
+                        match o.adopter {
+                            Adopter::Init => format!("init ({})", o.adopter_pid),
+                            Adopter::Subreaper => format!("subreaper {} ({})", o.adopter_name, o.adopter_pid),
+                        },
+                        o.session_id.to_string(),
+                        crate::format_duration(o.age_secs),
+                    ],
+                    &["PID", "Nombre", "Usuario", "Adoptado por", "Sesión", "Edad"],
+                ));
+                // La sugerencia se repite para todos los de un mismo adoptante: una línea por sugerencia
+                let mut suggestions: Vec<(&str, Vec<String>)> = Vec::new();
+                for o in &report.orphans {
+                    let pid = o.process.pid.to_string();
+                    match suggestions.iter_mut().find(|(s, _)| *s == o.suggestion) {
+                        Some((_, pids)) => pids.push(pid),
+                        None => suggestions.push((&o.suggestion, vec![pid])),
+                    }
+                }
+                for (suggestion, pids) in suggestions {
+                    output.push_str(&format!("  → PID {}: {}\n", pids.join(", "), suggestion));
+                }
+            }
+            Ok(output)
+        }
+        _ => Err(ExportError::UnsupportedFormat(format.to_string())),
+    }
+}
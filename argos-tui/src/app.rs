@@ -10,9 +10,11 @@ use crate::widgets::header::Header;
 use crate::widgets::footer::Footer;
 use argos_core::commands::list::list_processes;
 use argos_core::process::model::ProcessRow;
-use crate::stats::{ProcessStats, StatisticalMetrics};
+use crate::stats::ProcessStats;
 use std::collections::{HashMap, VecDeque};
 
+type SharedProcessData = Arc<Mutex<Option<(Vec<ProcessRow>, usize)>>>;
+
 pub enum Mode {
     Normal,
     Search,
@@ -26,7 +28,7 @@ pub struct App {
     pub last_update: Instant,
     pub update_interval: Duration,
     pub process_count: usize,
-    pub processes_data: Arc<Mutex<Option<(Vec<ProcessRow>, usize)>>>,
+    pub processes_data: SharedProcessData,
     pub all_processes: Vec<ProcessRow>,
     pub filtered_processes: Vec<ProcessRow>, // New field to track filtered processes
     pub mode: Mode,
@@ -40,6 +42,12 @@ pub struct App {
     pub filter_history: VecDeque<String>,
 }
 
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl App {
     pub fn new() -> Self {
         let (header, initial_data, widths, count) = Self::load_process_data();
@@ -90,7 +98,7 @@ impl App {
         processes.iter().map(Self::process_to_row).collect()
     }
 
-    fn start_background_updater(processes_data: Arc<Mutex<Option<(Vec<ProcessRow>, usize)>>>) {
+    fn start_background_updater(processes_data: SharedProcessData) {
         thread::spawn(move || {
             loop {
                 thread::sleep(Duration::from_secs(2));
@@ -201,7 +209,7 @@ impl App {
                         6 => format!("{:.2}", process.read_disk_usage).to_lowercase().contains(&query_lower),
                         7 => format!("{:.2}", process.write_disk_usage).to_lowercase().contains(&query_lower),
                         8 => process.start_time_human.to_lowercase().contains(&query_lower),
-                        9 => process.parent_pid.map_or(false, |pp| pp.to_string().to_lowercase().contains(&query_lower)),
+                        9 => process.parent_pid.is_some_and(|pp| pp.to_string().to_lowercase().contains(&query_lower)),
                         10 => format!("{:.2}", process.virtual_memory_mb).to_lowercase().contains(&query_lower),
                         _ => false,
                     }
@@ -240,7 +248,7 @@ impl App {
 
     pub fn refresh_processes(&mut self) {
         let mut data = self.processes_data.lock().unwrap();
-        if let Some((new_process_data, new_count)) = data.take() {
+        if let Some((new_process_data, _new_count)) = data.take() {
             self.all_processes = new_process_data;
             
             let (filtered_rows, filtered_processes) = if !self.search_query.is_empty() {
@@ -411,18 +419,16 @@ impl App {
             KeyCode::Char('+') => {
                 self.update_interval += Duration::from_secs(1);
             },
-            KeyCode::Char('-') => {
-                if self.update_interval > Duration::from_secs(1) {
-                    self.update_interval -= Duration::from_secs(1);
-                }
+            KeyCode::Char('-') if self.update_interval > Duration::from_secs(1) => {
+                self.update_interval -= Duration::from_secs(1);
             },
             KeyCode::Down => self.process_table.select_down(),
             KeyCode::Up => self.process_table.select_up(),
             KeyCode::Enter => {
-                if let Some(selected) = self.process_table.state.selected() {
-                    if let Some(process) = self.filtered_processes.get(selected) {
-                        self.show_process_details(process.pid);
-                    }
+                if let Some(selected) = self.process_table.state.selected()
+                    && let Some(process) = self.filtered_processes.get(selected)
+                {
+                    self.show_process_details(process.pid);
                 }
             },
             _ => {}
@@ -494,15 +500,15 @@ impl App {
             },
             KeyCode::Char('r') => {
                 let process = self.get_process_by_pid(pid).cloned();
-                if let Some(stats) = self.process_stats.get_mut(&pid) {
-                    if let Some(process) = process {
-                        stats.add_sample(
-                            process.cpu_usage,
-                            process.memory_mb,
-                            process.read_disk_usage,
-                            process.write_disk_usage,
-                        );
-                    }
+                if let Some(stats) = self.process_stats.get_mut(&pid)
+                    && let Some(process) = process
+                {
+                    stats.add_sample(
+                        process.cpu_usage,
+                        process.memory_mb,
+                        process.read_disk_usage,
+                        process.write_disk_usage,
+                    );
                 }
             },
             _ => {}
@@ -525,7 +531,7 @@ impl App {
                     6 => format!("{:.2}", process.read_disk_usage).to_lowercase().contains(&query_lower),
                     7 => format!("{:.2}", process.write_disk_usage).to_lowercase().contains(&query_lower),
                     8 => process.start_time_human.to_lowercase().contains(&query_lower),
-                    9 => process.parent_pid.map_or(false, |pp| pp.to_string().to_lowercase().contains(&query_lower)),
+                    9 => process.parent_pid.is_some_and(|pp| pp.to_string().to_lowercase().contains(&query_lower)),
                     10 => format!("{:.2}", process.virtual_memory_mb).to_lowercase().contains(&query_lower),
                     _ => false,
                 }
@@ -572,15 +578,15 @@ impl App {
             .collect();
 
         for (pid, maybe_process) in process_data {
-            if let Some(process) = maybe_process {
-                if let Some(stats) = self.process_stats.get_mut(&pid) {
-                    stats.add_sample(
-                        process.cpu_usage,
-                        process.memory_mb,
-                        process.read_disk_usage,
-                        process.write_disk_usage,
-                    );
-                }
+            if let Some(process) = maybe_process
+                && let Some(stats) = self.process_stats.get_mut(&pid)
+            {
+                stats.add_sample(
+                    process.cpu_usage,
+                    process.memory_mb,
+                    process.read_disk_usage,
+                    process.write_disk_usage,
+                );
             }
         }
 
@@ -595,17 +601,17 @@ impl App {
         self.selected_pid = Some(pid);
         self.mode = Mode::ProcessDetail(pid);
         
-        if !self.process_stats.contains_key(&pid) {
-            if let Some(process) = self.get_process_by_pid(pid) {
-                let mut stats = ProcessStats::new(pid, process.name.clone(), 60);
-                stats.add_sample(
-                    process.cpu_usage,
-                    process.memory_mb,
-                    process.read_disk_usage,
-                    process.write_disk_usage,
-                );
-                self.process_stats.insert(pid, stats);
-            }
+        if !self.process_stats.contains_key(&pid)
+            && let Some(process) = self.get_process_by_pid(pid)
+        {
+            let mut stats = ProcessStats::new(pid, process.name.clone(), 60);
+            stats.add_sample(
+                process.cpu_usage,
+                process.memory_mb,
+                process.read_disk_usage,
+                process.write_disk_usage,
+            );
+            self.process_stats.insert(pid, stats);
         }
     }
 
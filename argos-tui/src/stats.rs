@@ -1,6 +1,8 @@
 use std::collections::VecDeque;
 use std::time::{Instant, Duration};
 
+use argos_core::stats::linear_regression;
+
 #[derive(Debug, Clone)]
 pub struct ProcessStats {
     pub pid: u32,
@@ -131,27 +133,14 @@ impl ProcessStats {
     }
 
     fn linear_regression(&self, values: &[f64]) -> (f64, f64) {
-        let n = values.len() as f64;
-        if n < 2.0 {
+        if values.len() < 2 {
             return (0.0, *values.last().unwrap_or(&0.0));
         }
 
-        let x_mean = (n - 1.0) / 2.0;
-        let y_mean = values.iter().sum::<f64>() / n;
-
-        let mut numerator = 0.0;
-        let mut denominator = 0.0;
-
-        for (i, &y) in values.iter().enumerate() {
-            let x = i as f64 - x_mean;
-            numerator += x * (y - y_mean);
-            denominator += x * x;
-        }
-
-        let slope = if denominator == 0.0 { 0.0 } else { numerator / denominator };
-        let next_value = y_mean + slope * (x_mean + 1.0);
-
-        (slope, next_value)
+        // Pendiente por muestra y valor previsto para la siguiente
+        let x: Vec<f64> = (0..values.len()).map(|i| i as f64).collect();
+        let fit = linear_regression(&x, values);
+        (fit.slope, fit.predict(values.len() as f64))
     }
 
     pub fn get_cpu_metrics(&self) -> StatisticalMetrics {
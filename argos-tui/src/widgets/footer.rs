@@ -12,7 +12,7 @@ impl Footer {
         Self { text: text.into() }
     }
 
-    pub fn render(&self) -> Paragraph {
+    pub fn render(&self) -> Paragraph<'_> {
         Paragraph::new(self.text.clone())
             .block(
                 Block::default()
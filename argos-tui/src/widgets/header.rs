@@ -9,7 +9,7 @@ impl Header {
         Self { text: text.into() }
     }
 
-    pub fn render(&self) -> Paragraph {
+    pub fn render(&self) -> Paragraph<'_> {
         Paragraph::new(self.text.clone())
             .block(ratatui::widgets::Block::default().borders(Borders::ALL))
     }
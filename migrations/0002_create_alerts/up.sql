@@ -0,0 +1,21 @@
+-- Alertas del watchdog: un registro por incidente (regla + proceso)
+CREATE TABLE alerts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    rule TEXT NOT NULL,
+    pid INTEGER NOT NULL,
+    process_name TEXT NOT NULL,
+    process_start_time BIGINT,     -- junto al PID identifica el proceso (los PIDs se reutilizan)
+    process_cmd TEXT,
+    condition TEXT NOT NULL,
+    cpu_usage REAL NOT NULL,       -- valores al abrirse la alerta
+    memory_bytes BIGINT NOT NULL,
+    peak_cpu_usage REAL NOT NULL,  -- máximos mientras estuvo activa
+    peak_memory_bytes BIGINT NOT NULL,
+    actions TEXT NOT NULL,         -- JSON: acciones ejecutadas y su resultado
+    started_at TIMESTAMP NOT NULL,
+    ended_at TIMESTAMP,
+    acked_at TIMESTAMP,
+    ack_note TEXT
+);
+
+CREATE INDEX idx_alerts_started_at ON alerts(started_at);
@@ -0,0 +1,7 @@
+ALTER TABLE samples DROP COLUMN process;
+ALTER TABLE samples DROP COLUMN write_bytes_delta;
+ALTER TABLE samples DROP COLUMN read_bytes_delta;
+ALTER TABLE samples DROP COLUMN cpu_time_delta_ms;
+ALTER TABLE samples DROP COLUMN monotonic_secs;
+ALTER TABLE samples DROP COLUMN captured_at;
+ALTER TABLE samples DROP COLUMN pid;
@@ -0,0 +1,9 @@
+-- Registro completo de cada muestra (SampleRecord). `timestamp` sigue siendo el
+-- tiempo relativo al inicio de la sesión y `memory` los bytes de memoria residente.
+ALTER TABLE samples ADD COLUMN pid INTEGER;                -- proceso muestreado (la sesión puede agrupar varios)
+ALTER TABLE samples ADD COLUMN captured_at TIMESTAMP;      -- instante de reloj (UTC)
+ALTER TABLE samples ADD COLUMN monotonic_secs DOUBLE;      -- reloj monótono del sistema
+ALTER TABLE samples ADD COLUMN cpu_time_delta_ms BIGINT;   -- tiempo de CPU consumido desde la muestra anterior
+ALTER TABLE samples ADD COLUMN read_bytes_delta BIGINT;    -- bytes leídos desde la muestra anterior
+ALTER TABLE samples ADD COLUMN write_bytes_delta BIGINT;   -- bytes escritos desde la muestra anterior
+ALTER TABLE samples ADD COLUMN process TEXT;               -- JSON: ProcessRow completo